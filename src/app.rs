@@ -1,4 +1,6 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -6,17 +8,77 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tui_textarea::{CursorMove, TextArea};
 
-use crate::languages::{build_translation_prompt_with_signature, Language};
+use crate::clock::{Clock, SystemClock};
+use crate::events::{log_event, Event};
+use crate::languages::{
+    build_translation_prompt_any_source, build_translation_prompt_with_signature, Language,
+};
 use crate::llm;
-use crate::problem::{run_tests_on_piston, Problem, TestResults};
-use crate::syntax::SyntectHighlighter;
+use crate::log;
+use crate::problem::{run_tests_on_piston, Problem, TestCase, TestResults};
+use crate::syntax::HighlightCache;
+use crate::theme::Theme;
 
 // Configuration constants
 const LANGUAGE_CHANGE_INTERVAL_SECS: u64 = 15;
+const COUNTDOWN_SECS: u64 = 5;
+/// Seconds after a problem loads before the swap timer may fire
+/// (override with `BABEL_GRACE_SECS`)
+const DEFAULT_GRACE_SECS: u64 = 10;
+/// Longest frame delta the transition animations will advance by. A process
+/// suspension (Ctrl+Z) or load spike then loses time instead of teleporting
+/// the animation to its end.
+const MAX_FRAME_DELTA_SECS: f32 = 0.1;
+// Max keystrokes buffered while a transition animation is playing
+const KEY_BUFFER_CAP: usize = 64;
+/// Grading rubric defaults: points docked per hint consulted and per full
+/// minute on the round clock (`BABEL_GRADE_HINT_PENALTY` /
+/// `BABEL_GRADE_TIME_PENALTY` override them; zero both for lenient grading)
+const DEFAULT_GRADE_HINT_PENALTY: f32 = 5.0;
+const DEFAULT_GRADE_TIME_PENALTY: f32 = 2.0;
+
+// How long the mastery toast stays on screen
+const TOAST_SECS: u64 = 4;
+// How long the post-reveal language info card stays up
+const TIP_SECS: u64 = 3;
+// Submitting-bar increments per tick, by phase (see tick()'s Submitting
+// branch). The reveal increment is scaled by BABEL_SUBMIT_REVEAL_SPEED.
+const SUBMIT_COMPILE_INCREMENT: f32 = 0.025;
+const SUBMIT_WAIT_INCREMENT: f32 = 0.01;
+const SUBMIT_REVEAL_INCREMENT: f32 = 0.035;
+const SUBMIT_STALL_INCREMENT: f32 = 0.005;
+// Phase boundaries: 0-30% "compiling", 30-95% waiting on Piston, 95-100%
+// revealing results
+const SUBMIT_COMPILE_PHASE_END: f32 = 0.3;
+const SUBMIT_WAIT_PHASE_END: f32 = 0.95;
+// How long language swaps are paused after the LLM rate-limits us
+const RATE_LIMIT_COOLDOWN_SECS: u64 = 30;
+// Zen mode: how long before the deadline translation quietly starts, so it's
+// usually ready by the time the reveal finishes
+const ZEN_TRANSLATION_LEAD_SECS: u64 = 5;
+// Languages with a first 100% submission, persisted across sessions
+const MASTERY_FILE: &str = "babel_mastery.json";
+// Where the resumable session lives when BABEL_RESUME=1
+const SESSION_FILE: &str = "babel_session.json";
+// How often the resumable session is re-saved while coding
+const SESSION_SAVE_SECS: u64 = 10;
+// Default target for results export (override with BABEL_EXPORT_PATH)
+const EXPORT_FILE: &str = "babel_results.json";
+// Output panel protection against runaway prints (lines overridable with
+// BABEL_MAX_OUTPUT_LINES)
+const DEFAULT_MAX_OUTPUT_LINES: usize = 2000;
+// Consecutive sub-50% submissions before the reference solution is offered
+const SOLUTION_OFFER_FAILURES: usize = 3;
+// Rows the output panel occupies, borders included (override with
+// BABEL_OUTPUT_PANEL_HEIGHT); the auto-scroll window derives from this
+const DEFAULT_OUTPUT_PANEL_HEIGHT: u16 = 12;
+const MAX_OUTPUT_LINE_CHARS: usize = 500;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -26,6 +88,21 @@ pub enum AppState {
     Revealing(f32),          // 0.0 to 1.0 progress (reveal new language/problem)
     Submitting(f32, Option<TestResults>), // Combined: 0.0 to 1.0 progress with optional results
     Results(TestResults),
+    Stats,   // Session summary, reached with S from the results screen
+    History, // Scrollable submission history, reached with H from the results screen
+    DebugView, // Harness + raw Piston response, reached with V
+    ProblemSelect, // Keyboard-driven problem picker, opened with Ctrl+P while coding
+    ReviewTranslation, // Read-only preview of the translated code (BABEL_REVIEW_TRANSLATIONS=1)
+    SolutionView, // Read-only reference solution, offered after repeated failures
+}
+
+/// Actions reachable by clicking the footer shortcut labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FooterAction {
+    Submit,
+    NewProblem,
+    Run,
+    Quit,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +110,18 @@ pub enum ExecutionEvent {
     Log(OutputLine),
     Finished(TestResults),      // For submit - shows full results screen
     RunFinished(TestResults),    // For run - shows results in output panel
+    /// The exact harness sent to Piston and the raw response, for the
+    /// in-app debug overlay (same data as piston_full.log)
+    Debug { harness: String, raw_response: String },
+    /// Piston answered 429; run/submit pause for this many seconds
+    RateLimited(u64),
+}
+
+/// Which panel receives navigation keys in the coding view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Editor,
+    Problem,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +137,109 @@ pub struct OutputLine {
     pub is_error: bool,
 }
 
+/// Ad-hoc test-case entry (Ctrl+J while coding): one value per parameter,
+/// filled in order, then the expected output
+#[derive(Debug, Clone, Default)]
+pub struct CaseEntry {
+    /// Parameter values confirmed so far
+    pub inputs: Vec<String>,
+    /// The field currently being typed
+    pub buffer: String,
+    /// All parameters are in; the buffer now holds the expected output
+    pub expecting_output: bool,
+}
+
+/// Letter grade distilled from the round's metrics (see [`App::grade`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    S,
+    A,
+    B,
+    C,
+    F,
+}
+
+impl Grade {
+    fn from_score(score: f32) -> Grade {
+        if score >= 100.0 {
+            Grade::S
+        } else if score >= 85.0 {
+            Grade::A
+        } else if score >= 70.0 {
+            Grade::B
+        } else if score >= 50.0 {
+            Grade::C
+        } else {
+            Grade::F
+        }
+    }
+
+    fn letter(&self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::F => "F",
+        }
+    }
+
+    fn color(&self, theme: &Theme) -> Color {
+        match self {
+            Grade::S => theme.gold,
+            Grade::A => theme.success,
+            Grade::B => theme.purple,
+            Grade::C => theme.warn,
+            Grade::F => theme.error,
+        }
+    }
+}
+
+/// One submission, recorded for the in-session history overlay
+#[derive(Debug, Clone)]
+pub struct SubmissionRecord {
+    pub problem: String,
+    pub language: Language,
+    pub passed: usize,
+    pub total: usize,
+    pub at: chrono::DateTime<chrono::Local>,
+}
+
+/// Accumulated over the session and shown on the stats screen:
+/// languages the tower forced on the player and how they fared in each
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    pub rounds: usize,
+    pub languages_seen: Vec<Language>,
+    /// (language, cases passed, cases run) per submission language
+    pub per_language: Vec<(Language, usize, usize)>,
+    pub fastest_solve: Option<Duration>,
+}
+
+impl SessionStats {
+    fn record_language(&mut self, language: Language) {
+        self.languages_seen.push(language);
+    }
+
+    fn record_submission(&mut self, language: Language, passed: usize, total: usize, elapsed: Duration) {
+        self.rounds += 1;
+        match self.per_language.iter_mut().find(|(l, _, _)| *l == language) {
+            Some(entry) => {
+                entry.1 += passed;
+                entry.2 += total;
+            }
+            None => self.per_language.push((language, passed, total)),
+        }
+        // Fastest full-pass solve, measured from the last language change
+        if total > 0 && passed == total {
+            self.fastest_solve = Some(match self.fastest_solve {
+                Some(best) => best.min(elapsed),
+                None => elapsed,
+            });
+        }
+    }
+}
+
 /// Generate box-drawing ASCII art for a single letter
 fn get_letter_ascii(letter: char) -> Vec<String> {
     match letter.to_ascii_uppercase() {
@@ -259,6 +451,111 @@ fn get_letter_ascii(letter: char) -> Vec<String> {
             "███████╗".to_string(),
             "═╚═════╝".to_string(),
         ],
+        '0' => vec![
+            " ██████╗ ".to_string(),
+            "██╔═████╗".to_string(),
+            "██║██╔██║".to_string(),
+            "████╔╝██║".to_string(),
+            "╚██████╔╝".to_string(),
+            " ╚═════╝ ".to_string(),
+        ],
+        '1' => vec![
+            " ██╗".to_string(),
+            "███║".to_string(),
+            "╚██║".to_string(),
+            " ██║".to_string(),
+            " ██║".to_string(),
+            " ╚═╝".to_string(),
+        ],
+        '2' => vec![
+            "██████╗ ".to_string(),
+            "╚════██╗".to_string(),
+            " █████╔╝".to_string(),
+            "██╔═══╝ ".to_string(),
+            "███████╗".to_string(),
+            "╚══════╝".to_string(),
+        ],
+        '3' => vec![
+            "██████╗ ".to_string(),
+            "╚════██╗".to_string(),
+            " █████╔╝".to_string(),
+            " ╚═══██╗".to_string(),
+            "██████╔╝".to_string(),
+            "╚═════╝ ".to_string(),
+        ],
+        '4' => vec![
+            "██╗  ██╗".to_string(),
+            "██║  ██║".to_string(),
+            "███████║".to_string(),
+            "╚════██║".to_string(),
+            "     ██║".to_string(),
+            "     ╚═╝".to_string(),
+        ],
+        '5' => vec![
+            "███████╗".to_string(),
+            "██╔════╝".to_string(),
+            "███████╗".to_string(),
+            "╚════██║".to_string(),
+            "███████║".to_string(),
+            "╚══════╝".to_string(),
+        ],
+        '6' => vec![
+            " ██████╗ ".to_string(),
+            "██╔════╝ ".to_string(),
+            "███████╗ ".to_string(),
+            "██╔═══██╗".to_string(),
+            "╚██████╔╝".to_string(),
+            " ╚═════╝ ".to_string(),
+        ],
+        '7' => vec![
+            "███████╗".to_string(),
+            "╚════██║".to_string(),
+            "    ██╔╝".to_string(),
+            "   ██╔╝ ".to_string(),
+            "   ██║  ".to_string(),
+            "   ╚═╝  ".to_string(),
+        ],
+        '8' => vec![
+            " █████╗ ".to_string(),
+            "██╔══██╗".to_string(),
+            "╚█████╔╝".to_string(),
+            "██╔══██╗".to_string(),
+            "╚█████╔╝".to_string(),
+            " ╚════╝ ".to_string(),
+        ],
+        '9' => vec![
+            " █████╗ ".to_string(),
+            "██╔══██╗".to_string(),
+            "╚██████║".to_string(),
+            " ╚═══██║".to_string(),
+            " █████╔╝".to_string(),
+            " ╚════╝ ".to_string(),
+        ],
+        // Symbols that plausibly show up in future language names (C++, C#)
+        '+' => vec![
+            "       ".to_string(),
+            "  ██╗  ".to_string(),
+            "██████╗".to_string(),
+            "╚═██╔═╝".to_string(),
+            "  ╚═╝  ".to_string(),
+            "       ".to_string(),
+        ],
+        '#' => vec![
+            " ██╗ ██╗ ".to_string(),
+            "████████╗".to_string(),
+            "╚██╔═██╔╝".to_string(),
+            "████████╗".to_string(),
+            "╚██╔═██╔╝".to_string(),
+            " ╚═╝ ╚═╝ ".to_string(),
+        ],
+        '-' => vec![
+            "        ".to_string(),
+            "        ".to_string(),
+            "███████╗".to_string(),
+            "╚══════╝".to_string(),
+            "        ".to_string(),
+            "        ".to_string(),
+        ],
         ' ' => vec![
             "  ".to_string(),
             "  ".to_string(),
@@ -307,7 +604,10 @@ fn get_text_ascii(text: &str) -> Vec<String> {
     result
 }
 
-/// Generate ASCII art for a language name using composed letters
+/// Generate ASCII art for a language name using composed letters. Lookup is
+/// case-insensitive (`get_letter_ascii` uppercases per character) and digits
+/// and the symbols `+`/`#`/`-` have real glyphs, so names like "C#" or "C++"
+/// would render cleanly rather than as the fallback box.
 fn get_language_ascii(lang: &str) -> Vec<String> {
     let ascii = get_text_ascii(lang);
     // Add an empty line at the start for spacing
@@ -318,8 +618,8 @@ fn get_language_ascii(lang: &str) -> Vec<String> {
 }
 
 /// Generate starter code template for a problem in a specific language
-fn get_starter_code(problem: &Problem, language: Language) -> String {
-    let func_name = &problem.function_name;
+pub(crate) fn get_starter_code(problem: &Problem, language: Language) -> String {
+    let func_name = &problem.function_name_for(language);
     
     match language {
         Language::Python => {
@@ -549,6 +849,17 @@ pub struct App {
     pub state: AppState,
     pub last_randomize: Instant,
     pub randomize_interval: Duration,
+    /// When the current problem was loaded; swaps are held off for
+    /// `grace_period` after this so a fresh problem can be read in peace
+    pub problem_loaded_at: Instant,
+    pub grace_period: Duration,
+    /// Opt-in auto-run (`BABEL_AUTO_RUN=<secs>`): tests run automatically
+    /// that many seconds after the last keystroke, without pressing Ctrl+C
+    pub auto_run_debounce: Option<Duration>,
+    /// Last edit in the coding view; cleared once an auto-run fires
+    pub last_edit: Option<Instant>,
+    /// When `tick` last ran, for delta-based animation progress
+    pub last_tick_at: Instant,
     pub test_results: Option<TestResults>,
     pub scroll_offset: usize,
     pub transition_start: Option<Instant>,
@@ -559,7 +870,18 @@ pub struct App {
     pub execution_output: Vec<OutputLine>,
     pub execution_progress: f32,
     pub show_output_panel: bool,
+    /// Wrap long output lines instead of truncating them (Ctrl+L). Off by
+    /// default: one enormous error line would otherwise expand into many
+    /// rendered rows and fight the line-based `scroll_offset`
+    pub wrap_output: bool,
     pub editor_area: Rect,
+    /// Footer row plus the x-spans of its clickable shortcut labels,
+    /// recorded at render time for mouse hit-testing
+    pub footer_area: Rect,
+    pub footer_buttons: Vec<(u16, u16, FooterAction)>,
+    /// Set when a quit action fires outside the key path (footer click);
+    /// the main loop exits on it
+    pub should_quit: bool,
     pub countdown_start: Option<Instant>,
     pub pending_language: Option<Language>,
     pub pending_problem: Option<Problem>,
@@ -567,10 +889,331 @@ pub struct App {
     pub pending_translation: Option<TranslationEvent>,
     pub code_sent_for_translation: Option<String>,
     pub editor_scroll: usize,
+    pub buffered_keys: Vec<KeyEvent>,
+    pub translation_available: bool,
+    pub problem_scroll: usize,
+    pub focus: Focus,
+    pub offline: bool,
+    pub theme: Theme,
+    pub accessible_labels: bool,
+    pub reduced_motion: bool,
+    pub glitch: GlitchField,
+    /// Drives problem/language selection. Seeded from `BABEL_SEED` when set
+    /// so sessions are reproducible; otherwise seeded from entropy.
+    pub rng: StdRng,
+    pub stats: SessionStats,
+    pub mastered: HashSet<Language>,
+    /// One-time celebratory toast shown over the coding screen
+    pub toast: Option<(String, Instant)>,
+    /// In-session submissions, oldest first (rendered newest first)
+    pub history: Vec<SubmissionRecord>,
+    pub history_scroll: usize,
+    /// Scroll position of the results-screen scoreboard
+    pub results_scroll: usize,
+    /// Consecutive full submissions on the current problem scoring under 50%;
+    /// at [`SOLUTION_OFFER_FAILURES`] the reference solution is offered
+    pub consecutive_failures: usize,
+    pub solution_scroll: usize,
+    /// Learning aids on (disable with `BABEL_NO_HINTS=1` for a pure challenge)
+    pub hints_enabled: bool,
+    /// How many of the current problem's hints have been revealed (Ctrl+H)
+    pub hints_revealed: usize,
+    pub show_hints_overlay: bool,
+    /// When the post-reveal language info card appeared; expires after
+    /// [`TIP_SECS`] or on Esc
+    pub tip_shown_at: Option<Instant>,
+    /// Session persistence on (`BABEL_RESUME=1`): saved periodically and on
+    /// quit, restored in `new()`
+    pub resume_enabled: bool,
+    pub last_session_save: Instant,
+    /// Multiplier on the 95-100% results reveal speed
+    /// (`BABEL_SUBMIT_REVEAL_SPEED`, default 1.0)
+    pub submit_reveal_speed: f32,
+    /// Grading rubric weights (see [`DEFAULT_GRADE_HINT_PENALTY`])
+    pub grade_hint_penalty: f32,
+    pub grade_time_penalty: f32,
+    /// Per-line syntax highlight memoization for the editor
+    pub highlight_cache: HighlightCache,
+    /// Cap on `execution_output`; oldest lines are dropped past this
+    pub max_output_lines: usize,
+    /// How many lines the cap has dropped this run, rendered as a single
+    /// marker row at the top of the panel
+    pub output_lines_dropped: usize,
+    /// Output panel rows, borders included (`BABEL_OUTPUT_PANEL_HEIGHT`)
+    pub output_panel_height: u16,
+    /// Last run's (harness, raw response) for the debug overlay
+    pub debug_info: Option<(String, String)>,
+    pub debug_scroll: usize,
+    /// Spaces per indent level (`BABEL_TAB_WIDTH`, default 4)
+    pub tab_width: usize,
+    /// Insert literal tabs instead of spaces (`BABEL_USE_TABS=1`)
+    pub use_tabs: bool,
+    /// Show the Examples section of the problem panel. Toggled with `e`
+    /// while the panel has focus; starts hidden with `BABEL_HIDE_EXAMPLES=1`
+    /// for solving from the description alone.
+    pub show_examples: bool,
+    /// Session-local extra test cases for the current problem (Ctrl+J);
+    /// runs and submits include them after the built-in cases
+    pub custom_cases: Vec<TestCase>,
+    /// In-progress ad-hoc case entry, when the overlay is open
+    pub case_entry: Option<CaseEntry>,
+    /// Keep the last submission's scoreboard docked beside the editor
+    /// (entered with `c` from the results screen, toggled with Ctrl+G)
+    pub show_results_sidebar: bool,
+    /// What the docked sidebar shows — frozen at the submission that
+    /// produced it, so the comparison target doesn't move while editing
+    pub sidebar_results: Option<TestResults>,
+    /// Armed by Ctrl+O: the next terminal paste replaces the whole buffer
+    /// and is treated as an external solution to translate from
+    pub load_paste_armed: bool,
+    /// Language the external solution was pasted in; manual translation
+    /// (Ctrl+T) uses it as the source until the next swap
+    pub external_source: Option<Language>,
+    /// Source-language picker shown after an external solution loads
+    pub show_source_picker: bool,
+    pub source_picker_index: usize,
+    /// Show the Constraints section of the problem panel. Toggled with `c`
+    /// while the panel has focus.
+    pub show_constraints: bool,
+    /// Cursor position in the problem select menu
+    pub problem_select_index: usize,
+    /// Pause after the reveal to preview the translation before it is
+    /// applied (`BABEL_REVIEW_TRANSLATIONS=1`)
+    pub review_translations: bool,
+    pub review_scroll: usize,
+    /// While set and in the future, language swaps are skipped (set after a
+    /// rate-limited translation)
+    pub translation_cooldown_until: Option<Instant>,
+    /// While set and in the future, run/submit are refused (set after
+    /// Piston answers 429; emkc.org rate-limits per IP)
+    pub piston_cooldown_until: Option<Instant>,
+    /// Zen pacing (`BABEL_ZEN=1`): no countdown pressure — the round runs
+    /// uninterrupted and the swap lands all at once at the deadline
+    pub zen_mode: bool,
+    /// Weight the language roulette toward languages seen least often this
+    /// session (`BABEL_BALANCE_LANGS=1`); off by default so the pure-random
+    /// roulette is unchanged
+    pub balance_languages: bool,
+    /// Single-language practice (`BABEL_PRACTICE_LANG=<lang>`): the roulette
+    /// never fires, but Ctrl+T translates whatever is in the buffer — often
+    /// pasted from another language — into the pinned language
+    pub practice_mode: bool,
+    /// Round counter, bumped on restart/transition. Receivers created in an
+    /// older generation are dropped unread, so a still-running Piston or
+    /// translation task can't leak stale results into a fresh round.
+    pub generation: u64,
+    /// Generation `output_rx` was created in
+    pub output_generation: u64,
+    /// Generation `translation_rx` was created in
+    pub translation_generation: u64,
+    /// Time source for everything the app schedules; tests swap in a
+    /// [`crate::clock::ManualClock`] to drive timers deterministically
+    pub clock: Arc<dyn Clock>,
+}
+
+/// Pre-generated noise reused by the glitch renderers. Rolling a fresh
+/// `rand::random` per cell per frame costs thousands of RNG calls at 60fps
+/// on a large terminal; instead the field is generated once per resize and
+/// only a slice of it is re-rolled each frame, which shimmers the same way
+/// at a fraction of the CPU. Row strings are reused across frames too.
+pub struct GlitchField {
+    noise: Vec<f32>,
+    rows: Vec<String>,
+    width: usize,
+    height: usize,
+    refresh_cursor: usize,
+}
+
+impl GlitchField {
+    fn new() -> Self {
+        GlitchField {
+            noise: Vec::new(),
+            rows: Vec::new(),
+            width: 0,
+            height: 0,
+            refresh_cursor: 0,
+        }
+    }
+
+    /// Regenerate the field when the terminal is resized
+    fn ensure_size(&mut self, width: usize, height: usize) {
+        if self.width != width || self.height != height {
+            self.width = width;
+            self.height = height;
+            self.noise = (0..width * height).map(|_| rand::random::<f32>()).collect();
+            self.rows = vec![String::with_capacity(width * 3); height];
+            self.refresh_cursor = 0;
+        }
+    }
+
+    /// Re-roll roughly an eighth of the cells so the static keeps moving
+    /// without touching every cell every frame
+    fn advance(&mut self) {
+        if self.noise.is_empty() {
+            return;
+        }
+        let step = (self.noise.len() / 8).max(1);
+        for _ in 0..step {
+            self.noise[self.refresh_cursor] = rand::random::<f32>();
+            self.refresh_cursor = (self.refresh_cursor + 1) % self.noise.len();
+        }
+    }
+
+    /// Per-row jitter value, used in place of per-row `rand::random` calls
+    /// for saturation/brightness variation
+    fn jitter(&self, row: usize) -> f32 {
+        if self.noise.is_empty() {
+            return 0.5;
+        }
+        self.noise[(row * self.width) % self.noise.len()]
+    }
+
+    /// Rebuild each row in place: a cell shows `ch` when its noise value is
+    /// below `density(row, col)`, otherwise a space
+    fn fill_rows(&mut self, ch: &str, density: impl Fn(usize, usize) -> f32) {
+        for i in 0..self.height {
+            let row = &mut self.rows[i];
+            row.clear();
+            for j in 0..self.width {
+                if self.noise[i * self.width + j] < density(i, j) {
+                    row.push_str(ch);
+                } else {
+                    row.push(' ');
+                }
+            }
+        }
+    }
+
+    fn row(&self, i: usize) -> &str {
+        &self.rows[i]
+    }
+}
+
+/// HSV to RGB conversion shared by the glitch/reveal rainbow effects
+fn hsv_to_rgb(hue: f32, saturation: f32, brightness: f32) -> Color {
+    let c = brightness * saturation;
+    let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = brightness - c;
+
+    let (r, g, b) = if hue < 60.0 {
+        (c, x, 0.0)
+    } else if hue < 120.0 {
+        (x, c, 0.0)
+    } else if hue < 180.0 {
+        (0.0, c, x)
+    } else if hue < 240.0 {
+        (0.0, x, c)
+    } else if hue < 300.0 {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+
+    Color::Rgb(
+        ((r + m) * 255.0) as u8,
+        ((g + m) * 255.0) as u8,
+        ((b + m) * 255.0) as u8,
+    )
+}
+
+/// Snapshot persisted for `BABEL_RESUME=1`: enough to pick up the same
+/// problem, language and editor contents after a relaunch
+#[derive(Serialize, Deserialize)]
+struct SavedSession {
+    problem_id: usize,
+    language: Language,
+    code: String,
+}
+
+fn load_session() -> Option<SavedSession> {
+    std::fs::read_to_string(SESSION_FILE)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn load_mastery() -> HashSet<Language> {
+    std::fs::read_to_string(MASTERY_FILE)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Vec<Language>>(&s).ok())
+        .map(|v| v.into_iter().collect())
+        .unwrap_or_default()
+}
+
+fn save_mastery(mastered: &HashSet<Language>) {
+    let list: Vec<Language> = mastered.iter().copied().collect();
+    if let Ok(json) = serde_json::to_string(&list) {
+        let _ = std::fs::write(MASTERY_FILE, json);
+    }
+}
+
+/// Map each footer shortcut span ("^S", "^R", …) plus its caption span to a
+/// clickable x-range. The line is centered, so x depends on the total width.
+fn footer_hit_boxes(spans: &[Span], area: Rect) -> Vec<(u16, u16, FooterAction)> {
+    let widths: Vec<u16> = spans
+        .iter()
+        .map(|s| s.content.chars().count() as u16)
+        .collect();
+    let total: u16 = widths.iter().sum();
+    let start_x = area.x + area.width.saturating_sub(total) / 2;
+
+    let mut buttons = Vec::new();
+    for (i, span) in spans.iter().enumerate() {
+        let action = match span.content.as_ref() {
+            "^S" => FooterAction::Submit,
+            "^R" => FooterAction::NewProblem,
+            "^C" => FooterAction::Run,
+            "^Q" => FooterAction::Quit,
+            _ => continue,
+        };
+        let last = (i + 1).min(spans.len() - 1);
+        let from = start_x + widths[..i].iter().sum::<u16>();
+        let to = start_x + widths[..=last].iter().sum::<u16>();
+        buttons.push((from, to, action));
+    }
+    buttons
+}
+
+/// Minimal standard-alphabet base64, enough for OSC 52 clipboard payloads;
+/// hand-rolled to save a dependency for one call site
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = ((chunk[0] as u32) << 16)
+            | ((chunk.get(1).copied().unwrap_or(0) as u32) << 8)
+            | chunk.get(2).copied().unwrap_or(0) as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+/// Hard-truncate a line to `width` columns with a trailing ellipsis. Counts
+/// chars rather than display columns — good enough for the mostly-ASCII
+/// output the panel shows, and avoids pulling in a width crate.
+fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if width == 0 || text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(width.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Classify an error message as a connectivity failure (reqwest connect
+/// errors surface as these strings from both the Piston and LLM paths)
+fn is_connect_failure(msg: &str) -> bool {
+    msg.contains("Network Error")
+        || msg.contains("error sending request")
+        || msg.contains("connection")
+        || msg.contains("connect")
 }
 
 impl App {
-    fn build_editor_with_text(text: &str) -> TextArea<'static> {
+    fn build_editor_with_text(text: &str, tab_width: usize, use_tabs: bool) -> TextArea<'static> {
         let mut lines: Vec<String> = text
             .split('\n')
             .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
@@ -579,7 +1222,8 @@ impl App {
             lines.push(String::new());
         }
         let mut editor = TextArea::new(lines);
-        editor.set_tab_length(4);
+        editor.set_tab_length(tab_width as u8);
+        editor.set_hard_tab_indent(use_tabs);
         editor
     }
 
@@ -587,6 +1231,40 @@ impl App {
         self.editor.lines().join("\n")
     }
 
+    /// The current problem and buffer as a shareable text block — title,
+    /// description, examples, and the code fenced with its language — for
+    /// pasting into an external editor or a chat
+    fn problem_share_block(&self) -> String {
+        let mut block = format!("## {}\n\n{}\n", self.problem.title, self.problem.description);
+        if !self.problem.examples.is_empty() {
+            block.push_str("\nExamples:\n");
+            for example in &self.problem.examples {
+                block.push_str("  ");
+                block.push_str(example);
+                block.push('\n');
+            }
+        }
+        block.push_str(&format!(
+            "\n```{}\n{}\n```\n",
+            self.current_language.display_name().to_lowercase(),
+            self.code_text()
+        ));
+        block
+    }
+
+    /// Copy text to the system clipboard via OSC 52, which asks the terminal
+    /// emulator itself to do the copy — no clipboard crate needed, and it
+    /// works over SSH. The editor's yank buffer is loaded too so Ctrl+V
+    /// pastes the same text in-app even if the terminal ignores OSC 52.
+    fn copy_to_clipboard(&mut self, text: &str) {
+        use std::io::Write;
+
+        self.editor.set_yank_text(text);
+        let mut stdout = std::io::stdout();
+        let _ = write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+        let _ = stdout.flush();
+    }
+
     fn line_number_width(&self) -> usize {
         let digits = self.editor.lines().len().to_string().len();
         digits.max(2)
@@ -597,7 +1275,7 @@ impl App {
     }
 
     fn set_editor_content_with_cursor(&mut self, text: &str, cursor: Option<(usize, usize)>) {
-        self.editor = Self::build_editor_with_text(text);
+        self.editor = Self::build_editor_with_text(text, self.tab_width, self.use_tabs);
         if let Some((row, col)) = cursor {
             let max_row = self.editor.lines().len().saturating_sub(1);
             let target_row = row.min(max_row);
@@ -615,17 +1293,69 @@ impl App {
     }
 
     pub fn new() -> Self {
-        let current_language = Language::Python;
-        let problem = Problem::random();
-        let starter = get_starter_code(&problem, current_language);
-        
+        let practice_language = std::env::var("BABEL_PRACTICE_LANG")
+            .ok()
+            .and_then(|name| Language::from_name(&name));
+        let mut current_language = practice_language.unwrap_or(Language::Python);
+        let mut rng = match std::env::var("BABEL_SEED")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        // Opt-in resume: restore the last session's problem, language and
+        // editor contents. A stale problem id falls back to a random one.
+        let resume_enabled = std::env::var("BABEL_RESUME").map(|v| v == "1").unwrap_or(false);
+        let saved = if resume_enabled { load_session() } else { None };
+        let (problem, saved_code) = match saved {
+            Some(session) => match Problem::all().into_iter().find(|p| p.id == session.problem_id) {
+                Some(problem) => {
+                    // A pinned practice language still wins over the saved one
+                    if practice_language.is_none() {
+                        current_language = session.language;
+                    }
+                    (problem, Some(session.code))
+                }
+                None => (Problem::random_with_rng(&mut rng), None),
+            },
+            None => (Problem::random_with_rng(&mut rng), None),
+        };
+        let starter = saved_code.unwrap_or_else(|| get_starter_code(&problem, current_language));
+
+        let tab_width = std::env::var("BABEL_TAB_WIDTH")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|w| (1..=8).contains(w))
+            .unwrap_or(4);
+        let use_tabs = std::env::var("BABEL_USE_TABS")
+            .map(|v| v == "1")
+            .unwrap_or(false);
+
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        let now = clock.now();
         Self {
             problem: problem.clone(),
-            editor: Self::build_editor_with_text(&starter),
+            editor: Self::build_editor_with_text(&starter, tab_width, use_tabs),
             current_language,
             state: AppState::Coding,
-            last_randomize: Instant::now(),
+            last_randomize: now,
             randomize_interval: Duration::from_secs(LANGUAGE_CHANGE_INTERVAL_SECS),
+            problem_loaded_at: now,
+            auto_run_debounce: std::env::var("BABEL_AUTO_RUN")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            last_edit: None,
+            last_tick_at: now,
+            grace_period: Duration::from_secs(
+                std::env::var("BABEL_GRACE_SECS")
+                    .ok()
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(DEFAULT_GRACE_SECS),
+            ),
             test_results: None,
             scroll_offset: 0,
             transition_start: None,
@@ -634,7 +1364,11 @@ impl App {
             execution_output: Vec::new(),
             execution_progress: 0.0,
             show_output_panel: false,
+            wrap_output: false,
             editor_area: Rect::default(),
+            footer_area: Rect::default(),
+            footer_buttons: Vec::new(),
+            should_quit: false,
             countdown_start: None,
             pending_language: None,
             pending_problem: None,
@@ -642,24 +1376,199 @@ impl App {
             pending_translation: None,
             code_sent_for_translation: None,
             editor_scroll: 0,
+            buffered_keys: Vec::new(),
+            // Either provider can serve translations, keyed from the
+            // environment or the config file (see llm.rs)
+            translation_available: llm::gemini_key().is_some() || llm::openai_key().is_some(),
+            problem_scroll: 0,
+            focus: Focus::Editor,
+            offline: false,
+            theme: Theme::from_env(),
+            // Text labels instead of hue-only pass/fail indicators
+            accessible_labels: std::env::var("BABEL_ACCESSIBLE")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            // Swap the glitch animations for a calm fade (and lower the tick rate)
+            reduced_motion: std::env::var("BABEL_REDUCED_MOTION")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            glitch: GlitchField::new(),
+            rng,
+            stats: SessionStats::default(),
+            mastered: load_mastery(),
+            toast: None,
+            history: Vec::new(),
+            history_scroll: 0,
+            results_scroll: 0,
+            consecutive_failures: 0,
+            solution_scroll: 0,
+            hints_revealed: 0,
+            show_hints_overlay: false,
+            tip_shown_at: None,
+            resume_enabled,
+            last_session_save: now,
+            submit_reveal_speed: std::env::var("BABEL_SUBMIT_REVEAL_SPEED")
+                .ok()
+                .and_then(|s| s.parse::<f32>().ok())
+                .filter(|&speed| speed > 0.0)
+                .unwrap_or(1.0),
+            grade_hint_penalty: std::env::var("BABEL_GRADE_HINT_PENALTY")
+                .ok()
+                .and_then(|s| s.parse::<f32>().ok())
+                .filter(|&p| p >= 0.0)
+                .unwrap_or(DEFAULT_GRADE_HINT_PENALTY),
+            grade_time_penalty: std::env::var("BABEL_GRADE_TIME_PENALTY")
+                .ok()
+                .and_then(|s| s.parse::<f32>().ok())
+                .filter(|&p| p >= 0.0)
+                .unwrap_or(DEFAULT_GRADE_TIME_PENALTY),
+            hints_enabled: !std::env::var("BABEL_NO_HINTS")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            highlight_cache: HighlightCache::new(current_language),
+            max_output_lines: std::env::var("BABEL_MAX_OUTPUT_LINES")
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(DEFAULT_MAX_OUTPUT_LINES),
+            output_lines_dropped: 0,
+            output_panel_height: std::env::var("BABEL_OUTPUT_PANEL_HEIGHT")
+                .ok()
+                .and_then(|s| s.parse::<u16>().ok())
+                // 3 = borders plus one content row; much taller starves the editor
+                .filter(|h| (3..=30).contains(h))
+                .unwrap_or(DEFAULT_OUTPUT_PANEL_HEIGHT),
+            debug_info: None,
+            debug_scroll: 0,
+            tab_width,
+            use_tabs,
+            show_examples: !std::env::var("BABEL_HIDE_EXAMPLES")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            custom_cases: Vec::new(),
+            case_entry: None,
+            show_results_sidebar: false,
+            sidebar_results: None,
+            load_paste_armed: false,
+            external_source: None,
+            show_source_picker: false,
+            source_picker_index: 0,
+            show_constraints: true,
+            problem_select_index: 0,
+            review_translations: std::env::var("BABEL_REVIEW_TRANSLATIONS")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            review_scroll: 0,
+            translation_cooldown_until: None,
+            piston_cooldown_until: None,
+            zen_mode: std::env::var("BABEL_ZEN").map(|v| v == "1").unwrap_or(false),
+            balance_languages: std::env::var("BABEL_BALANCE_LANGS")
+                .map(|v| v == "1")
+                .unwrap_or(false),
+            practice_mode: practice_language.is_some(),
+            generation: 0,
+            output_generation: 0,
+            translation_generation: 0,
+            clock,
         }
     }
 
+    /// Time elapsed on the app clock since `earlier`; the clock-aware
+    /// equivalent of `Instant::elapsed`
+    fn elapsed_since(&self, earlier: Instant) -> Duration {
+        self.clock.now().saturating_duration_since(earlier)
+    }
+
     pub fn tick(&mut self) {
         self.glitch_frame = (self.glitch_frame + 1) % 10;
 
+        // Real time since the last tick, capped so suspensions don't
+        // fast-forward the delta-driven animations below
+        let now = self.clock.now();
+        let frame_dt = now
+            .saturating_duration_since(self.last_tick_at)
+            .as_secs_f32()
+            .min(MAX_FRAME_DELTA_SECS);
+        self.last_tick_at = now;
+
+        // Expire the mastery toast
+        if let Some((_, shown_at)) = &self.toast {
+            if now.saturating_duration_since(*shown_at) >= Duration::from_secs(TOAST_SECS) {
+                self.toast = None;
+            }
+        }
+
+        // Expire the language info card
+        if let Some(shown_at) = self.tip_shown_at {
+            if now.saturating_duration_since(shown_at) >= Duration::from_secs(TIP_SECS) {
+                self.tip_shown_at = None;
+            }
+        }
+
         match self.state {
             AppState::Coding => {
-                let elapsed = self.last_randomize.elapsed();
-                // Start countdown 5 seconds before randomize time
-                let countdown_threshold = self.randomize_interval.saturating_sub(Duration::from_secs(5));
-                if elapsed >= countdown_threshold && self.countdown_start.is_none() {
-                    self.start_countdown();
+                // With a single allowed language (or a pinned practice
+                // language) there is nothing to swap to, so skip the
+                // countdown/transition entirely
+                if !self.practice_mode && self.current_language.has_alternate() {
+                    // Rate-limit cooldown also pushes back the swap timer so
+                    // the countdown doesn't fire the moment it expires
+                    if self.translation_cooldown_remaining().is_some() {
+                        self.last_randomize = now;
+                    }
+                    // Grace period after a problem loads: hold the timer back
+                    // so a swap can't land while the problem is still being read
+                    if self.elapsed_since(self.problem_loaded_at) < self.grace_period {
+                        self.last_randomize = now;
+                    }
+                    let elapsed = self.elapsed_since(self.last_randomize);
+                    if self.zen_mode {
+                        // No countdown: quietly pick the language and start
+                        // translating shortly before the deadline, then jump
+                        // straight into the transition when it hits
+                        let lead = self
+                            .randomize_interval
+                            .saturating_sub(Duration::from_secs(ZEN_TRANSLATION_LEAD_SECS));
+                        if elapsed >= lead && self.pending_language.is_none() {
+                            self.pending_language = Some(self.next_random_language());
+                            self.start_llm_translation();
+                        }
+                        if elapsed >= self.randomize_interval {
+                            self.start_transition();
+                        }
+                    } else {
+                        // Start countdown COUNTDOWN_SECS seconds before randomize time
+                        let countdown_threshold = self.randomize_interval.saturating_sub(Duration::from_secs(COUNTDOWN_SECS));
+                        if elapsed >= countdown_threshold && self.countdown_start.is_none() {
+                            self.start_countdown();
+                        }
+                    }
+                }
+                // Periodic session save while coding (cheap: one small file)
+                if self.resume_enabled
+                    && self.elapsed_since(self.last_session_save)
+                        >= Duration::from_secs(SESSION_SAVE_SECS)
+                {
+                    self.save_session();
+                    self.last_session_save = now;
+                }
+                // Auto-run once typing has been idle past the debounce.
+                // Skipped while a run is already in flight (`output_rx` live)
+                if let (Some(debounce), Some(last_edit)) = (self.auto_run_debounce, self.last_edit) {
+                    if now.saturating_duration_since(last_edit) >= debounce
+                        && self.output_rx.is_none()
+                    {
+                        self.last_edit = None;
+                        if !self.blocked_while_offline() {
+                            self.show_output_panel = true;
+                            self.run_code();
+                        }
+                    }
                 }
             }
             AppState::Countdown(count) => {
                 // Use the actual remaining time to stay in sync with the footer timer
-                let elapsed = self.last_randomize.elapsed();
+                let elapsed = self.elapsed_since(self.last_randomize);
                 let remaining = self.randomize_interval.saturating_sub(elapsed);
                 let new_count = remaining.as_secs() as u8;
                 
@@ -669,78 +1578,179 @@ impl App {
                     self.state = AppState::Countdown(new_count);
                 }
             }
-            AppState::Transitioning(_progress) => {
-                if let Some(start) = self.transition_start {
-                    let elapsed = start.elapsed().as_secs_f32();
-                    let new_progress = (elapsed / 1.5).min(1.0); // 1.5s transition
-                    
-                    if new_progress >= 1.0 {
-                        self.start_reveal();
-                    } else {
-                        self.state = AppState::Transitioning(new_progress);
-                    }
+            AppState::Transitioning(progress) if self.transition_start.is_some() => {
+                let new_progress = (progress + frame_dt / 1.5).min(1.0); // 1.5s transition
+
+                if new_progress >= 1.0 {
+                    self.start_reveal();
+                } else {
+                    self.state = AppState::Transitioning(new_progress);
                 }
             }
-            AppState::Revealing(_progress) => {
-                if let Some(start) = self.transition_start {
-                    let elapsed = start.elapsed().as_secs_f32();
-                    let new_progress = (elapsed / 3.0).min(1.0); // 3s reveal
-                    
-                    if new_progress >= 1.0 {
-                        if self.translation_ready() {
-                            self.complete_transition();
+            AppState::Revealing(progress) if self.transition_start.is_some() => {
+                let new_progress = (progress + frame_dt / 3.0).min(1.0); // 3s reveal
+
+                if new_progress >= 1.0 {
+                    if self.translation_ready() {
+                        // Optionally pause here so the translation can be
+                        // studied before it replaces the editor contents
+                        if self.review_translations
+                            && self.pending_language.is_some()
+                            && matches!(self.pending_translation, Some(TranslationEvent::Success(_)))
+                        {
+                            self.review_scroll = 0;
+                            self.state = AppState::ReviewTranslation;
+                            log_event(Event::StateChanged { state: "review_translation".to_string() });
                         } else {
-                            // Keep showing the final reveal (don't restart animation)
-                            // Just stay at progress 0.99 to show the language while waiting
-                            self.state = AppState::Revealing(0.99);
+                            self.complete_transition();
                         }
                     } else {
-                        self.state = AppState::Revealing(new_progress);
+                        // Keep showing the final reveal (don't restart animation)
+                        // Just stay at progress 0.99 to show the language while waiting
+                        self.state = AppState::Revealing(0.99);
                     }
+                } else {
+                    self.state = AppState::Revealing(new_progress);
                 }
             }
             AppState::Submitting(mut progress, ref results) => {
                 // Continuous progress through all phases
-                let increment = if progress < 0.3 {
-                    0.025  // Compiling phase: 0-30%
-                } else if progress < 0.95 && results.is_none() {
-                    0.01   // Running tests phase: 30-95% (slower while waiting for results)
-                } else if results.is_some() {
-                    0.035  // Revealing results phase: 95-100% (faster reveal)
+                let increment = if results.is_some() {
+                    // Revealing results: faster, and user-tunable
+                    SUBMIT_REVEAL_INCREMENT * self.submit_reveal_speed
+                } else if progress < SUBMIT_COMPILE_PHASE_END {
+                    SUBMIT_COMPILE_INCREMENT
+                } else if progress < SUBMIT_WAIT_PHASE_END {
+                    SUBMIT_WAIT_INCREMENT // Slower while waiting on Piston
                 } else {
-                    0.005  // Very slow crawl if stuck at 95% without results
+                    SUBMIT_STALL_INCREMENT // Very slow crawl if stuck without results
                 };
-                
+
                 progress += increment;
-                
+
+                // Hold at the compile-phase boundary until the run has
+                // actually produced output, so the first stretch of the bar
+                // tracks something real rather than pure elapsed time
+                if results.is_none()
+                    && self.execution_output.is_empty()
+                    && progress > SUBMIT_COMPILE_PHASE_END
+                {
+                    progress = SUBMIT_COMPILE_PHASE_END;
+                }
+
                 if progress >= 1.0 && results.is_some() {
+                    self.results_scroll = 0;
                     self.state = AppState::Results(results.clone().unwrap());
+                    log_event(Event::StateChanged { state: "results".to_string() });
                 } else {
                     // Cap at 95% until we have results
-                    if results.is_none() && progress > 0.95 {
-                        progress = 0.95;
+                    if results.is_none() && progress > SUBMIT_WAIT_PHASE_END {
+                        progress = SUBMIT_WAIT_PHASE_END;
                     }
                     self.state = AppState::Submitting(progress, results.clone());
                 }
             }
+            // The translation can vanish under the review screen (e.g. a
+            // stale generation dropped it); bail out of review from the
+            // event path so rendering stays read-only
+            AppState::ReviewTranslation
+                if !matches!(self.pending_translation, Some(TranslationEvent::Success(_))) =>
+            {
+                self.complete_transition();
+            }
             _ => {}
         }
     }
+    /// Append a program output line, clamping line length and dropping the
+    /// oldest lines past the cap so pathological programs can't bog down
+    /// rendering
+    fn push_output_line(&mut self, mut line: OutputLine) {
+        if line.text.chars().count() > MAX_OUTPUT_LINE_CHARS {
+            line.text = line.text.chars().take(MAX_OUTPUT_LINE_CHARS).collect();
+            line.text.push('…');
+        }
+        self.execution_output.push(line);
+        if self.execution_output.len() > self.max_output_lines {
+            let excess = self.execution_output.len() - self.max_output_lines;
+            self.execution_output.drain(0..excess);
+            // Counted rather than written over a surviving line; the panel
+            // renders the tally as its own marker row
+            self.output_lines_dropped += excess;
+        }
+    }
+
     pub fn poll_execution(&mut self) {
+        // A receiver from a previous round only carries stale results; drop
+        // it (and the sender's task output with it) unread
+        if self.output_rx.is_some() && self.output_generation != self.generation {
+            self.output_rx = None;
+            return;
+        }
+
         let mut should_close = false;
-        if let Some(rx) = &mut self.output_rx {
+        // Take the receiver out so event handling can borrow self mutably
+        if let Some(mut rx) = self.output_rx.take() {
             while let Ok(event) = rx.try_recv() {
                 match event {
                     ExecutionEvent::Log(line) => {
-                        self.execution_output.push(line);
-                        // Auto-scroll
-                        if self.execution_output.len() > 10 {
-                           self.scroll_offset = self.execution_output.len() - 10;
+                        // Track connectivity from the execution pipeline
+                        if line.is_error && is_connect_failure(&line.text) {
+                            self.offline = true;
+                        } else if !line.is_error && line.text.starts_with("Completed in") {
+                            self.offline = false;
+                        }
+                        self.push_output_line(line);
+                        // Auto-scroll to keep the newest line inside the
+                        // panel (content rows = panel height minus borders)
+                        let visible = self.output_panel_height.saturating_sub(2) as usize;
+                        let rendered = self.execution_output.len()
+                            + (self.output_lines_dropped > 0) as usize;
+                        if visible > 0 && rendered > visible {
+                            self.scroll_offset = rendered - visible;
                         }
                     }
                     ExecutionEvent::Finished(results) => {
                         // Submit mode - update Submitting state with results
                         self.test_results = Some(results.clone());
+                        // Track repeated failures for the solution offer
+                        // (single-case reruns don't count either way)
+                        if results.total == self.problem.test_cases.len() + self.custom_cases.len() {
+                            if results.score_percent() < 50 {
+                                self.consecutive_failures += 1;
+                            } else {
+                                self.consecutive_failures = 0;
+                            }
+                        }
+                        self.stats.record_submission(
+                            self.current_language,
+                            results.passed,
+                            results.total,
+                            self.elapsed_since(self.last_randomize),
+                        );
+                        log_event(Event::Submission {
+                            problem: self.problem.title.clone(),
+                            language: self.current_language.display_name().to_string(),
+                            passed: results.passed,
+                            total: results.total,
+                        });
+                        self.history.push(SubmissionRecord {
+                            problem: self.problem.title.clone(),
+                            language: self.current_language,
+                            passed: results.passed,
+                            total: results.total,
+                            at: chrono::Local::now(),
+                        });
+                        // First flawless submission in this language?
+                        if results.total > 0
+                            && results.passed == results.total
+                            && self.mastered.insert(self.current_language)
+                        {
+                            save_mastery(&self.mastered);
+                            self.toast = Some((
+                                format!("◈ First flawless in {}! ◈", self.current_language.display_name()),
+                                self.clock.now(),
+                            ));
+                        }
                         if let AppState::Submitting(progress, _) = self.state {
                             // Jump to 95% if not there yet, then let it animate to 100%
                             let new_progress = progress.max(0.95);
@@ -777,7 +1787,13 @@ impl App {
                         
                         // Add individual test results
                         for detail in &results.details {
-                            let status = if detail.passed { "✓ PASS" } else { "✗ FAIL" };
+                            let status = if self.accessible_labels {
+                                if detail.passed { "[PASS]" } else { "[FAIL]" }
+                            } else if detail.passed {
+                                "✓ PASS"
+                            } else {
+                                "✗ FAIL"
+                            };
                             let status_line = format!("{} Test #{}", status, detail.case_number);
                             self.execution_output.push(OutputLine { 
                                 text: status_line, 
@@ -801,17 +1817,29 @@ impl App {
                         }
                         should_close = true;
                     }
+                    ExecutionEvent::RateLimited(secs) => {
+                        self.piston_cooldown_until =
+                            Some(self.clock.now() + Duration::from_secs(secs));
+                    }
+                    ExecutionEvent::Debug { harness, raw_response } => {
+                        self.debug_info = Some((harness, raw_response));
+                        self.debug_scroll = 0;
+                    }
                 }
             }
-        }
-        
-        if should_close {
-            self.output_rx = None;
-        }
 
+            if !should_close {
+                self.output_rx = Some(rx);
+            }
+        }
     }
 
     pub fn poll_translation(&mut self) {
+        if self.translation_rx.is_some() && self.translation_generation != self.generation {
+            self.translation_rx = None;
+            return;
+        }
+
         let mut completed = None;
         if let Some(rx) = &mut self.translation_rx {
             while let Ok(event) = rx.try_recv() {
@@ -820,6 +1848,28 @@ impl App {
         }
 
         if let Some(event) = completed {
+            match &event {
+                TranslationEvent::Success(_) => self.offline = false,
+                TranslationEvent::Failure(msg) => {
+                    // Rate limits get a cooldown so the next swaps don't run
+                    // straight into the same wall
+                    if llm::is_rate_limited(msg) {
+                        log::warn(
+                            "Translation",
+                            &format!("rate-limited, pausing swaps {}s: {}", RATE_LIMIT_COOLDOWN_SECS, msg),
+                        );
+                        self.translation_cooldown_until =
+                            Some(self.clock.now() + Duration::from_secs(RATE_LIMIT_COOLDOWN_SECS));
+                        self.toast = Some((
+                            "⚠ Translation rate-limited — keeping current language".to_string(),
+                            self.clock.now(),
+                        ));
+                    }
+                    if is_connect_failure(msg) {
+                        self.offline = true;
+                    }
+                }
+            }
             self.pending_translation = Some(event);
             self.translation_rx = None;
         }
@@ -829,6 +1879,48 @@ impl App {
         self.pending_translation.is_some()
     }
 
+    /// Whether a translation task is still running in the background,
+    /// whatever state the UI happens to be in
+    fn translation_in_flight(&self) -> bool {
+        self.translation_rx.is_some()
+    }
+
+    /// Time left on the post-rate-limit cooldown, if one is active
+    fn translation_cooldown_remaining(&self) -> Option<Duration> {
+        let until = self.translation_cooldown_until?;
+        let now = self.clock.now();
+        if until > now {
+            Some(until - now)
+        } else {
+            None
+        }
+    }
+
+    /// Time left on the post-429 execution cooldown, if one is active
+    fn piston_cooldown_remaining(&self) -> Option<Duration> {
+        let until = self.piston_cooldown_until?;
+        let now = self.clock.now();
+        if until > now {
+            Some(until - now)
+        } else {
+            None
+        }
+    }
+
+    /// Frame of the braille spinner shown while background work runs
+    fn spinner_glyph(&self) -> &'static str {
+        match self.glitch_frame % 8 {
+            0 => "⠋",
+            1 => "⠙",
+            2 => "⠹",
+            3 => "⠸",
+            4 => "⠼",
+            5 => "⠴",
+            6 => "⠦",
+            _ => "⠧",
+        }
+    }
+
     fn start_llm_translation(&mut self) {
         // Don't clear pending_translation here - only replace when new result arrives
         // This prevents losing a completed translation if we restart
@@ -841,17 +1933,25 @@ impl App {
 
         let code = self.code_text();
         self.code_sent_for_translation = Some(code.clone());
-        let from = self.current_language;
+        // An externally loaded solution (Ctrl+O) overrides the nominal source
+        let from = self.external_source.unwrap_or(self.current_language);
         let to = target_language;
-        if from == to {
+        // In practice mode "from" is nominally the pinned language but the
+        // buffer may hold pasted code from anywhere, so translate anyway
+        if from == to && !self.practice_mode {
             self.pending_translation = Some(TranslationEvent::Success(code));
             return;
         }
 
         let type_sig = self.problem.type_signature();
-        let prompt = build_translation_prompt_with_signature(&code, from, to, Some(&type_sig));
+        let prompt = if self.practice_mode && self.external_source.is_none() {
+            build_translation_prompt_any_source(&code, to, Some(&type_sig))
+        } else {
+            build_translation_prompt_with_signature(&code, from, to, Some(&type_sig))
+        };
         let (tx, rx) = mpsc::channel(1);
         self.translation_rx = Some(rx);
+        self.translation_generation = self.generation;
 
         tokio::spawn(async move {
             let result = llm::translate_code(&prompt).await;
@@ -863,71 +1963,295 @@ impl App {
         });
     }
 
+    /// The roulette draw: uniform over all-but-current by default, weighted
+    /// toward least-seen languages when balancing is on. Counts come from
+    /// the session's `languages_seen` log rather than a separate tally.
+    fn next_random_language(&mut self) -> Language {
+        if self.balance_languages {
+            let mut counts: std::collections::HashMap<Language, u32> =
+                std::collections::HashMap::new();
+            for lang in &self.stats.languages_seen {
+                *counts.entry(*lang).or_insert(0) += 1;
+            }
+            self.current_language
+                .random_weighted_with_rng(&counts, &mut self.rng)
+        } else {
+            self.current_language.random_except_with_rng(&mut self.rng)
+        }
+    }
+
     fn start_countdown(&mut self) {
-        self.countdown_start = Some(Instant::now());
-        self.state = AppState::Countdown(5);
+        self.countdown_start = Some(self.clock.now());
+        self.state = AppState::Countdown(COUNTDOWN_SECS as u8);
+        log_event(Event::StateChanged { state: "countdown".to_string() });
         // Pre-select new language now so we can show it during reveal
-        self.pending_language = Some(self.current_language.random_except());
+        self.pending_language = Some(self.next_random_language());
         // Translation will start when countdown finishes (in start_transition)
     }
 
     fn start_transition(&mut self) {
-        self.transition_start = Some(Instant::now());
+        // A no-op swap (possible if the allowed set ever shrinks so
+        // `random_except` lands back on the current language) shouldn't play
+        // the whole reveal theatre for nothing. Practice mode is exempt: its
+        // same-language "swap" is the mechanism that translates the buffer.
+        if !self.practice_mode && self.pending_language == Some(self.current_language) {
+            self.pending_language = None;
+            self.countdown_start = None;
+            self.last_randomize = self.clock.now();
+            self.state = AppState::Coding;
+            self.toast = Some(("◈ Language unchanged ◈".to_string(), self.clock.now()));
+            return;
+        }
+        self.transition_start = Some(self.clock.now());
         self.state = AppState::Transitioning(0.0);
-        // Start translation now that countdown has finished
-        self.start_llm_translation();
+        log_event(Event::StateChanged { state: "transitioning".to_string() });
+        // Start translation now that countdown has finished (zen mode kicks
+        // it off early, in which case it's already in flight)
+        if !self.translation_in_flight() && !self.translation_ready() {
+            self.start_llm_translation();
+        }
     }
 
     fn start_reveal(&mut self) {
-        self.transition_start = Some(Instant::now());
+        self.transition_start = Some(self.clock.now());
         self.state = AppState::Revealing(0.0);
+        log_event(Event::StateChanged { state: "revealing".to_string() });
     }
 
     fn complete_transition(&mut self) {
         // Apply the pending language only (keep the same problem)
         let cursor = self.editor.cursor();
+
+        // Translation was kicked off at the start of the transition with a
+        // snapshot of the editor. If the text somehow grew past that snapshot
+        // afterwards (keys during transitions are buffered, so this is a
+        // safety net), carry the extra suffix over onto the translated code.
+        let current_code = self.code_text();
+        let edit_suffix = self
+            .code_sent_for_translation
+            .as_ref()
+            .filter(|sent| *sent != &current_code)
+            .and_then(|sent| current_code.strip_prefix(sent.as_str()))
+            .map(|suffix| suffix.to_string());
+
         if let Some(new_lang) = self.pending_language.take() {
             if let Some(result) = self.pending_translation.take() {
                 match result {
                     TranslationEvent::Success(translated) => {
-                        self.set_editor_content_with_cursor(&translated, Some(cursor));
+                        // Reject garbled output before it reaches the editor;
+                        // keeping the old code beats installing broken code
+                        match new_lang.validate_translation(&translated) {
+                            Ok(()) => {
+                                let mut new_text = self.apply_indent_config(&translated);
+                                if let Some(suffix) = edit_suffix {
+                                    new_text.push_str(&suffix);
+                                }
+                                self.set_editor_content_with_cursor(&new_text, Some(cursor));
+                            }
+                            Err(reason) => {
+                                log::error(
+                                    &format!("Translation rejected ({})", new_lang.display_name()),
+                                    &reason,
+                                );
+                            }
+                        }
                     }
                     TranslationEvent::Failure(_) => {
                         // Keep the existing code if translation fails
                     }
                 }
             }
-            self.current_language = new_lang;
-        } 
-        
+            // Don't log a swap (or re-tip) when nothing actually changed;
+            // practice mode is the exception since its same-language pass
+            // is a real translation of the buffer
+            if new_lang != self.current_language || self.practice_mode {
+                log_event(Event::LanguageSwapped {
+                    from: self.current_language.display_name().to_string(),
+                    to: new_lang.display_name().to_string(),
+                });
+                self.current_language = new_lang;
+                self.stats.record_language(new_lang);
+                // Soften the landing in an unfamiliar language with a quick tip
+                if self.hints_enabled {
+                    self.tip_shown_at = Some(self.clock.now());
+                }
+            }
+        }
+
         // Clear any pending problem (not used in auto-transition)
         self.pending_problem = None;
         self.translation_rx = None;
         self.pending_translation = None;
+        // The external source only applies to the translation just landed
+        self.external_source = None;
+        self.generation += 1; // Orphaned LLM tasks from this round are now stale
         
         // Reset timer and state
-        self.last_randomize = Instant::now();
+        self.last_randomize = self.clock.now();
         self.state = AppState::Coding;
         self.transition_start = None;
         self.countdown_start = None;
+
+        // Replay any keystrokes buffered during the animation
+        let buffered = std::mem::take(&mut self.buffered_keys);
+        for key in buffered {
+            self.handle_coding_key(key);
+        }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) {
         match self.state {
             AppState::Coding | AppState::Countdown(_) => self.handle_coding_key(key),
+            AppState::Submitting(_, _) => self.handle_submitting_key(key),
             AppState::Results(_) => self.handle_results_key(key),
-             _ => {}, // Ignore input during transitions and execution
+            AppState::Stats => self.handle_stats_key(key),
+            AppState::History => self.handle_history_key(key),
+            AppState::DebugView => self.handle_debug_key(key),
+            AppState::ProblemSelect => self.handle_problem_select_key(key),
+            AppState::ReviewTranslation => self.handle_review_key(key),
+            AppState::SolutionView => self.handle_solution_key(key),
+            AppState::Transitioning(_) | AppState::Revealing(_) => {
+                // Buffer keystrokes typed during the animation and replay them
+                // once we're back in Coding ("keep typing" should mean it)
+                if self.buffered_keys.len() < KEY_BUFFER_CAP {
+                    self.buffered_keys.push(key);
+                }
+            }
         }
     }
 
-    fn randomize_problem(&mut self) {
-        let new_problem = self.problem.random_except();
-        self.problem = new_problem.clone();
+    /// Typing is ignored during submit; the only live key is Esc, which
+    /// cancels the in-flight run (while results are still pending) via the
+    /// generation counter so its late events are dropped unread
+    fn handle_submitting_key(&mut self, key: KeyEvent) {
+        if key.code != KeyCode::Esc {
+            return;
+        }
+        if let AppState::Submitting(_, None) = self.state {
+            self.generation += 1;
+            self.output_rx = None;
+            self.execution_progress = 0.0;
+            self.show_output_panel = true;
+            self.execution_output.push(OutputLine {
+                text: "Submission cancelled.".to_string(),
+                is_error: false,
+            });
+            self.state = AppState::Coding;
+        }
+    }
+
+    fn handle_review_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.review_scroll = self.review_scroll.saturating_sub(1),
+            KeyCode::Down => self.review_scroll += 1,
+            KeyCode::PageUp => self.review_scroll = self.review_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.review_scroll += 10,
+            KeyCode::Enter => {
+                // Accept: the normal transition path applies the translation
+                self.complete_transition();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                // Reject: drop the translation so the old code is kept
+                // (the language still swaps, as with a failed translation)
+                self.pending_translation = None;
+                self.complete_transition();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_problem_select_key(&mut self, key: KeyEvent) {
+        let count = Problem::all().len();
+        match key.code {
+            KeyCode::Up => {
+                self.problem_select_index = self.problem_select_index.saturating_sub(1);
+            }
+            KeyCode::Down if self.problem_select_index + 1 < count => {
+                self.problem_select_index += 1;
+            }
+            KeyCode::Enter => {
+                // Load the chosen problem with fresh starter code in the
+                // current language
+                if let Some(problem) = Problem::all().into_iter().nth(self.problem_select_index) {
+                    self.problem = problem;
+                    let starter = get_starter_code(&self.problem, self.current_language);
+                    self.set_editor_content(&starter);
+                    self.problem_scroll = 0;
+                    self.consecutive_failures = 0;
+                    self.hints_revealed = 0;
+                    self.show_hints_overlay = false;
+                    self.external_source = None;
+                    self.show_results_sidebar = false;
+                    self.sidebar_results = None;
+                    self.custom_cases.clear();
+                    self.case_entry = None;
+                    self.problem_loaded_at = self.clock.now();
+                }
+                self.state = AppState::Coding;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.state = AppState::Coding;
+            }
+            _ => {}
+        }
+    }
+
+    fn randomize_problem(&mut self) {
+        let new_problem = self.problem.random_except_with_rng(&mut self.rng);
+        self.problem = new_problem.clone();
         let starter = get_starter_code(&new_problem, self.current_language);
         self.set_editor_content(&starter);
+        self.problem_scroll = 0;
+        self.consecutive_failures = 0;
+        self.hints_revealed = 0;
+        self.show_hints_overlay = false;
+        self.external_source = None;
+        // A scoreboard from another problem is no comparison target, and
+        // ad-hoc cases only fit the problem they were written for
+        self.show_results_sidebar = false;
+        self.sidebar_results = None;
+        self.custom_cases.clear();
+        self.case_entry = None;
+        self.problem_loaded_at = self.clock.now();
+    }
+
+    /// Persist the resumable session (no-op unless `BABEL_RESUME=1`)
+    pub fn save_session(&self) {
+        if !self.resume_enabled {
+            return;
+        }
+        let session = SavedSession {
+            problem_id: self.problem.id,
+            language: self.current_language,
+            code: self.code_text(),
+        };
+        if let Ok(json) = serde_json::to_string(&session) {
+            let _ = std::fs::write(SESSION_FILE, json);
+        }
+    }
+
+    /// Letter grade for the round: the raw pass percentage docked by the
+    /// configured penalties for hints consulted and time on the clock
+    fn grade(&self, results: &TestResults) -> Grade {
+        let minutes = self.elapsed_since(self.last_randomize).as_secs_f32() / 60.0;
+        let score = results.score_percent() as f32
+            - self.grade_hint_penalty * self.hints_revealed as f32
+            - self.grade_time_penalty * minutes;
+        Grade::from_score(score)
+    }
+
+    /// Whether the results screen should offer the reference solution
+    fn solution_offer_active(&self) -> bool {
+        self.hints_enabled
+            && self.consecutive_failures >= SOLUTION_OFFER_FAILURES
+            && self.problem.reference_solution.is_some()
     }
 
     fn handle_coding_key(&mut self, key: KeyEvent) {
+        // Feed the auto-run debounce; any keystroke counts as activity
+        if self.auto_run_debounce.is_some() {
+            self.last_edit = Some(self.clock.now());
+        }
         // Smart detection: Try Cmd (SUPER) first, then Ctrl
         // Some terminals (with config) can pass through Cmd keys
         // Most terminals pass through Ctrl/Alt keys
@@ -939,6 +2263,105 @@ impl App {
         // Use Cmd OR Ctrl (whichever is available) for line/editing commands
         let has_modifier = is_cmd || is_ctrl;
 
+        // Ad-hoc case entry swallows everything so typing can't leak into
+        // the editor underneath
+        if self.case_entry.is_some() {
+            self.handle_case_entry_key(key);
+            return;
+        }
+
+        // Source-language picker for an externally loaded solution: it
+        // captures all keys so a stray keystroke can't edit the fresh paste
+        if self.show_source_picker {
+            match key.code {
+                KeyCode::Up => {
+                    self.source_picker_index = self.source_picker_index.saturating_sub(1)
+                }
+                KeyCode::Down => {
+                    self.source_picker_index =
+                        (self.source_picker_index + 1).min(Language::all().len() - 1)
+                }
+                KeyCode::Enter => {
+                    let source = Language::all()[self.source_picker_index];
+                    self.external_source = Some(source);
+                    self.show_source_picker = false;
+                    self.toast = Some((
+                        format!(
+                            "◈ Loaded external {} solution — ^T translates it ◈",
+                            source.display_name()
+                        ),
+                        self.clock.now(),
+                    ));
+                }
+                KeyCode::Esc => self.show_source_picker = false,
+                _ => {}
+            }
+            return;
+        }
+
+        // Hints overlay: Esc dismisses it, anything else falls through
+        if self.show_hints_overlay && key.code == KeyCode::Esc {
+            self.show_hints_overlay = false;
+            return;
+        }
+
+        // Esc dismisses the language info card early
+        if self.tip_shown_at.is_some() && key.code == KeyCode::Esc {
+            self.tip_shown_at = None;
+            return;
+        }
+
+        // Cmd/Ctrl+H reveals the problem's hints one at a time
+        if has_modifier && !is_alt && matches!(key.code, KeyCode::Char('h') | KeyCode::Char('H')) {
+            if self.hints_enabled && !self.problem.hints.is_empty() {
+                if self.show_hints_overlay || self.hints_revealed == 0 {
+                    // Already open (or first press): reveal the next one
+                    if self.hints_revealed < self.problem.hints.len() {
+                        self.hints_revealed += 1;
+                    }
+                }
+                self.show_hints_overlay = true;
+            }
+            return;
+        }
+
+        // Cmd/Ctrl+B cycles focus between the editor and the problem panel
+        if has_modifier && !is_alt && matches!(key.code, KeyCode::Char('b') | KeyCode::Char('B')) {
+            self.focus = match self.focus {
+                Focus::Editor => Focus::Problem,
+                Focus::Problem => Focus::Editor,
+            };
+            return;
+        }
+
+        // When the problem panel is focused, navigation keys scroll it and
+        // everything else is ignored so the editor isn't modified by accident
+        if self.focus == Focus::Problem {
+            match key.code {
+                KeyCode::Up => self.problem_scroll = self.problem_scroll.saturating_sub(1),
+                KeyCode::Down => self.problem_scroll += 1,
+                KeyCode::PageUp => self.problem_scroll = self.problem_scroll.saturating_sub(10),
+                KeyCode::PageDown => self.problem_scroll += 10,
+                // Toggle the Examples section for harder, description-only practice
+                KeyCode::Char('e') | KeyCode::Char('E') => self.show_examples = !self.show_examples,
+                // Toggle the Constraints section to keep the panel short
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    self.show_constraints = !self.show_constraints
+                }
+                // Yank the whole problem plus the current code as a
+                // shareable block (external editor, asking for help)
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    let block = self.problem_share_block();
+                    self.copy_to_clipboard(&block);
+                    self.toast =
+                        Some(("◈ Problem copied to clipboard ◈".to_string(), self.clock.now()));
+                }
+                KeyCode::Esc => self.focus = Focus::Editor,
+                _ => {}
+            }
+            return;
+        }
+
         if has_modifier && !is_alt {
             match key.code {
                 // Cmd/Ctrl+S to submit
@@ -959,7 +2382,7 @@ impl App {
                 KeyCode::Char('c') | KeyCode::Char('C') => {
                     if self.editor.is_selecting() {
                         self.editor.copy();
-                    } else {
+                    } else if !self.blocked_while_offline() {
                         self.show_output_panel = true;
                         self.run_code();
                     }
@@ -1017,6 +2440,73 @@ impl App {
                     self.editor.delete_next_char();
                     return;
                 }
+                // Cmd/Ctrl+J: add an ad-hoc test case for this problem
+                // (session-local; rides along on the next run/submit)
+                KeyCode::Char('j') | KeyCode::Char('J') => {
+                    self.case_entry = Some(CaseEntry::default());
+                    return;
+                }
+                // Cmd/Ctrl+G: toggle the docked last-results sidebar
+                KeyCode::Char('g') | KeyCode::Char('G') => {
+                    if self.sidebar_results.is_some() {
+                        self.show_results_sidebar = !self.show_results_sidebar;
+                    }
+                    return;
+                }
+                // Cmd/Ctrl+L: wrap long output lines instead of truncating
+                KeyCode::Char('l') | KeyCode::Char('L') => {
+                    self.wrap_output = !self.wrap_output;
+                    self.toast = Some((
+                        if self.wrap_output {
+                            "◈ Output wrapping on ◈".to_string()
+                        } else {
+                            "◈ Long output lines truncated ◈".to_string()
+                        },
+                        self.clock.now(),
+                    ));
+                    return;
+                }
+                // Cmd/Ctrl+O: load an external solution — the next paste
+                // replaces the buffer and its source language is recorded
+                // for manual translation (pairs with Ctrl+T)
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    self.load_paste_armed = !self.load_paste_armed;
+                    self.toast = Some((
+                        if self.load_paste_armed {
+                            "◈ Paste now — it will replace the buffer ◈".to_string()
+                        } else {
+                            "◈ Load cancelled ◈".to_string()
+                        },
+                        self.clock.now(),
+                    ));
+                    return;
+                }
+                // Cmd/Ctrl+P: pick a specific problem instead of a random one
+                KeyCode::Char('p') | KeyCode::Char('P') => {
+                    self.problem_select_index = Problem::all()
+                        .iter()
+                        .position(|p| p.id == self.problem.id)
+                        .unwrap_or(0);
+                    self.state = AppState::ProblemSelect;
+                    return;
+                }
+                // Cmd/Ctrl+T: swap languages now instead of waiting for the
+                // timer (Coding only — not mid-countdown). In practice mode
+                // the target is the pinned language itself.
+                KeyCode::Char('t') | KeyCode::Char('T') => {
+                    if self.state == AppState::Coding
+                        && self.translation_cooldown_remaining().is_none()
+                    {
+                        if self.practice_mode {
+                            self.pending_language = Some(self.current_language);
+                            self.start_transition();
+                        } else if self.current_language.has_alternate() {
+                            self.pending_language = Some(self.next_random_language());
+                            self.start_transition();
+                        }
+                    }
+                    return;
+                }
                 // Cmd/Ctrl+Left: move to start of line (macOS style)
                 KeyCode::Left if is_cmd => {
                     self.move_to_line_start();
@@ -1027,6 +2517,15 @@ impl App {
                     self.move_to_line_end();
                     return;
                 }
+                // Ctrl+Left/Right: word-wise movement (Cmd keeps line start/end)
+                KeyCode::Left => {
+                    self.editor.move_cursor(CursorMove::WordBack);
+                    return;
+                }
+                KeyCode::Right => {
+                    self.editor.move_cursor(CursorMove::WordForward);
+                    return;
+                }
                 // Cmd+Up: move to start of document (macOS style)
                 KeyCode::Up if is_cmd => {
                     self.editor.move_cursor(CursorMove::Top);
@@ -1041,6 +2540,38 @@ impl App {
             }
         }
 
+        // Alt+Up/Down scrolls the problem panel
+        if is_alt && !has_modifier {
+            match key.code {
+                KeyCode::Up => {
+                    self.problem_scroll = self.problem_scroll.saturating_sub(1);
+                    return;
+                }
+                KeyCode::Down => {
+                    self.problem_scroll += 1;
+                    return;
+                }
+                // Alt+V: open the run debug overlay (harness + raw response)
+                KeyCode::Char('v') | KeyCode::Char('V') => {
+                    if self.debug_info.is_some() {
+                        self.debug_scroll = 0;
+                        self.state = AppState::DebugView;
+                    }
+                    return;
+                }
+                // Alt+Left/Right: word-wise movement (macOS style)
+                KeyCode::Left => {
+                    self.editor.move_cursor(CursorMove::WordBack);
+                    return;
+                }
+                KeyCode::Right => {
+                    self.editor.move_cursor(CursorMove::WordForward);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
         if key.code == KeyCode::BackTab {
             self.unindent_current_line();
             return;
@@ -1071,18 +2602,273 @@ impl App {
                 self.state = AppState::Coding;
                 self.test_results = None;
                 self.execution_output.clear();
+                self.output_lines_dropped = 0;
                 self.show_output_panel = false;
                 self.execution_progress = 0.0;
                 self.output_rx = None;
-                self.last_randomize = Instant::now(); // Reset timer
+                self.generation += 1; // Invalidate any still-running tasks
+                self.last_randomize = self.clock.now(); // Reset timer
+                self.problem_loaded_at = self.clock.now();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                // New problem, same language: fresh starter code so the
+                // current language can be drilled on something else
+                self.state = AppState::Coding;
+                self.test_results = None;
+                self.execution_output.clear();
+                self.output_lines_dropped = 0;
+                self.show_output_panel = false;
+                self.execution_progress = 0.0;
+                self.output_rx = None;
+                self.generation += 1;
+                self.randomize_problem();
+                self.last_randomize = self.clock.now();
             }
             KeyCode::Esc | KeyCode::Char('q') => {
                 // Keep results visible, could add exit logic here
             }
+            KeyCode::Up => self.results_scroll = self.results_scroll.saturating_sub(1),
+            KeyCode::Down => self.results_scroll += 1,
+            KeyCode::PageUp => self.results_scroll = self.results_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.results_scroll += 10,
+            // Jump the scoreboard to the first failing trial
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                let jump = match &self.state {
+                    AppState::Results(results) => {
+                        results.details.iter().position(|d| !d.passed).map(|i| {
+                            // Entries render 4 lines when passed, 5 when
+                            // failed, after one leading blank line
+                            1 + results.details[..i]
+                                .iter()
+                                .map(|d| if d.passed { 4 } else { 5 })
+                                .sum::<usize>()
+                        })
+                    }
+                    _ => return,
+                };
+                match jump {
+                    Some(offset) => self.results_scroll = offset,
+                    None => {
+                        self.toast = Some(("◈ All trials passed ◈".to_string(), self.clock.now()));
+                    }
+                }
+            }
+            // Back to the editor with this scoreboard docked beside it —
+            // tweak and resubmit without losing what just failed
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                if let AppState::Results(results) = &self.state {
+                    self.sidebar_results = Some(results.clone());
+                }
+                self.show_results_sidebar = true;
+                self.state = AppState::Coding;
+                self.execution_progress = 0.0;
+                self.output_rx = None;
+                self.generation += 1;
+                // Unlike `r` the round clock keeps running: this is a
+                // mid-round tweak, not a restart
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                self.state = AppState::Stats;
+            }
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                self.history_scroll = 0;
+                self.state = AppState::History;
+            }
+            // Study the reference solution, offered after repeated failures
+            KeyCode::Char('l') | KeyCode::Char('L') if self.solution_offer_active() => {
+                self.solution_scroll = 0;
+                self.state = AppState::SolutionView;
+                log_event(Event::StateChanged { state: "solution_view".to_string() });
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                self.export_results();
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') if self.debug_info.is_some() => {
+                self.debug_scroll = 0;
+                self.state = AppState::DebugView;
+            }
+            // Digits 1-9 re-run just that test case for faster iteration
+            KeyCode::Char(c) if c.is_ascii_digit() && c != '0' => {
+                let idx = (c as u8 - b'1') as usize;
+                if idx < self.problem.test_cases.len() + self.custom_cases.len() {
+                    self.state = AppState::Coding;
+                    self.test_results = None;
+                    self.execution_progress = 0.0;
+                    self.output_rx = None;
+                    self.generation += 1;
+                    self.last_randomize = self.clock.now(); // Reset timer
+                    self.run_single_case(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+
+    /// Write the last run's full results as JSON for post-session analysis.
+    /// Path comes from `BABEL_EXPORT_PATH`, defaulting to `babel_results.json`.
+    fn export_results(&mut self) {
+        let results = match (&self.state, &self.test_results) {
+            (AppState::Results(r), _) => r.clone(),
+            (_, Some(r)) => r.clone(),
+            _ => return,
+        };
+        let path = std::env::var("BABEL_EXPORT_PATH").unwrap_or_else(|_| EXPORT_FILE.to_string());
+        let outcome = serde_json::to_string_pretty(&results)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        match outcome {
+            Ok(()) => {
+                self.toast = Some((format!("◈ Results exported to {} ◈", path), self.clock.now()));
+            }
+            Err(e) => {
+                log::error("Results export", &e);
+                self.toast = Some(("⚠ Export failed — see error log".to_string(), self.clock.now()));
+            }
+        }
+    }
+
+    fn handle_stats_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s') | KeyCode::Char('S') => {
+                // Back to the results screen we came from
+                if let Some(results) = self.test_results.clone() {
+                    self.state = AppState::Results(results);
+                } else {
+                    self.state = AppState::Coding;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_debug_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.debug_scroll = self.debug_scroll.saturating_sub(1),
+            KeyCode::Down => self.debug_scroll += 1,
+            KeyCode::PageUp => self.debug_scroll = self.debug_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.debug_scroll += 10,
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') | KeyCode::Char('V') => {
+                if let Some(results) = self.test_results.clone() {
+                    self.state = AppState::Results(results);
+                } else {
+                    self.state = AppState::Coding;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_solution_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.solution_scroll = self.solution_scroll.saturating_sub(1),
+            KeyCode::Down => self.solution_scroll += 1,
+            KeyCode::PageUp => self.solution_scroll = self.solution_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.solution_scroll += 10,
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('l') | KeyCode::Char('L') => {
+                if let Some(results) = self.test_results.clone() {
+                    self.state = AppState::Results(results);
+                } else {
+                    self.state = AppState::Coding;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_history_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.history_scroll = self.history_scroll.saturating_sub(1),
+            KeyCode::Down if self.history_scroll + 1 < self.history.len() => {
+                self.history_scroll += 1;
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Char('H') => {
+                // Back to the results screen we came from
+                if let Some(results) = self.test_results.clone() {
+                    self.state = AppState::Results(results);
+                } else {
+                    self.state = AppState::Coding;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys while the ad-hoc case entry overlay is open: type a value per
+    /// parameter (Enter confirms each), then the expected output
+    fn handle_case_entry_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.case_entry = None;
+            }
+            KeyCode::Backspace => {
+                if let Some(entry) = self.case_entry.as_mut() {
+                    entry.buffer.pop();
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(mut entry) = self.case_entry.take() {
+                    let value = entry.buffer.trim().to_string();
+                    if value.is_empty() {
+                        // Nothing typed yet; keep waiting
+                        self.case_entry = Some(entry);
+                    } else if entry.expecting_output {
+                        self.custom_cases.push(TestCase {
+                            input: entry.inputs,
+                            expected: value,
+                        });
+                        self.toast = Some((
+                            format!(
+                                "◈ Custom case #{} added ◈",
+                                self.problem.test_cases.len() + self.custom_cases.len()
+                            ),
+                            self.clock.now(),
+                        ));
+                    } else {
+                        entry.inputs.push(value);
+                        entry.buffer.clear();
+                        entry.expecting_output = entry.inputs.len() == self.problem.parameters.len();
+                        self.case_entry = Some(entry);
+                    }
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(entry) = self.case_entry.as_mut() {
+                    entry.buffer.push(c);
+                }
+            }
             _ => {}
         }
     }
 
+    /// Bracketed paste from the terminal. Inserted as a single editing
+    /// operation (one undo step) with `\r\n`/`\r` line endings normalized,
+    /// which the per-key path can't do — `build_editor_with_text` strips
+    /// `\r` on load, but pasted text would otherwise bypass that.
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.state != AppState::Coding || self.focus != Focus::Editor {
+            return;
+        }
+        let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.load_paste_armed {
+            // External solution load (Ctrl+O): replace the buffer wholesale
+            // and ask for the source language, with a guess preselected
+            self.load_paste_armed = false;
+            self.set_editor_content(&normalized);
+            let guess =
+                Language::guess_from_code(&normalized).unwrap_or(self.current_language);
+            self.source_picker_index = Language::all()
+                .iter()
+                .position(|&lang| lang == guess)
+                .unwrap_or(0);
+            self.show_source_picker = true;
+            return;
+        }
+        self.editor.insert_str(&normalized);
+        if self.auto_run_debounce.is_some() {
+            self.last_edit = Some(self.clock.now());
+        }
+    }
 
     pub fn handle_mouse(&mut self, mouse: MouseEvent) {
         if self.state != AppState::Coding {
@@ -1094,6 +2880,30 @@ impl App {
                 // Check if click is in editor area
                 let click_x = mouse.column;
                 let click_y = mouse.row;
+
+                // Footer shortcut labels act as buttons on click
+                if matches!(mouse.kind, MouseEventKind::Down(_))
+                    && click_y >= self.footer_area.y
+                    && click_y < self.footer_area.y + self.footer_area.height
+                {
+                    let action = self
+                        .footer_buttons
+                        .iter()
+                        .find(|(from, to, _)| click_x >= *from && click_x < *to)
+                        .map(|(_, _, action)| *action);
+                    match action {
+                        Some(FooterAction::Submit) => self.submit(),
+                        Some(FooterAction::NewProblem) => self.randomize_problem(),
+                        Some(FooterAction::Run) if !self.blocked_while_offline() => {
+                            self.show_output_panel = true;
+                            self.run_code();
+                        }
+                        Some(FooterAction::Quit) => self.should_quit = true,
+                        _ => {}
+                    }
+                    return;
+                }
+
                 let gutter_width = self.line_number_width() + 1;
                 
                 // Account for border (1 char) and line numbers (4 chars: " 99 ")
@@ -1116,49 +2926,103 @@ impl App {
                 }
             }
             MouseEventKind::ScrollUp => {
-                // Scroll up (move cursor up)
-                self.editor.move_cursor(CursorMove::Up);
+                // Alt+scroll scrolls the problem panel, plain scroll the editor
+                if mouse.modifiers.contains(KeyModifiers::ALT) {
+                    self.problem_scroll = self.problem_scroll.saturating_sub(1);
+                } else {
+                    self.editor.move_cursor(CursorMove::Up);
+                }
             }
             MouseEventKind::ScrollDown => {
-                // Scroll down (move cursor down)
-                self.editor.move_cursor(CursorMove::Down);
+                if mouse.modifiers.contains(KeyModifiers::ALT) {
+                    self.problem_scroll += 1;
+                } else {
+                    self.editor.move_cursor(CursorMove::Down);
+                }
             }
             _ => {}
         }
     }
 
+    /// Re-emit leading indentation of translated code using the configured
+    /// unit. The smallest non-zero indent in the text counts as one level.
+    fn apply_indent_config(&self, text: &str) -> String {
+        if !self.use_tabs && self.tab_width == 4 {
+            return text.to_string();
+        }
+
+        let unit = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.chars().take_while(|&c| c == ' ').count())
+            .filter(|&n| n > 0)
+            .min()
+            .unwrap_or(4);
+
+        text.lines()
+            .map(|line| {
+                let spaces = line.chars().take_while(|&c| c == ' ').count();
+                let levels = spaces / unit;
+                let remainder = spaces % unit;
+                let indent = if self.use_tabs {
+                    "\t".repeat(levels)
+                } else {
+                    " ".repeat(levels * self.tab_width)
+                };
+                format!("{}{}{}", indent, " ".repeat(remainder), &line[spaces..])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn insert_newline_with_indent(&mut self) {
         let (row, _) = self.editor.cursor();
         let lines = self.editor.lines();
         let current_line = lines.get(row).map(|line| line.as_str()).unwrap_or("");
 
-        let indent = current_line.chars().take_while(|&c| c == ' ').count();
+        // Carry over the exact leading whitespace (spaces or hard tabs)
+        let indent: String = current_line
+            .chars()
+            .take_while(|&c| c == ' ' || c == '\t')
+            .collect();
         self.editor.insert_newline();
-        if indent > 0 {
-            self.editor.insert_str(" ".repeat(indent));
+        if !indent.is_empty() {
+            self.editor.insert_str(indent);
         }
     }
 
-    /// Shared helper to execute code and run tests
-    fn execute_code(&mut self, is_submit: bool) {
+    /// Shared helper to execute code and run tests. When `case_index` is set,
+    /// only that single test case is sent to the harness.
+    fn execute_code_with_cases(&mut self, is_submit: bool, case_index: Option<usize>) {
         self.execution_output.clear();
-        self.execution_output.push(OutputLine { 
-            text: if is_submit { 
-                "Compiling and sending to Piston API...".to_string() 
-            } else { 
-                "Running code on Piston API...".to_string() 
-            }, 
-            is_error: false 
+        self.output_lines_dropped = 0;
+        self.execution_output.push(OutputLine {
+            text: if is_submit {
+                "Compiling and sending to Piston API...".to_string()
+            } else if let Some(idx) = case_index {
+                format!("Running test case #{} on Piston API...", idx + 1)
+            } else {
+                "Running code on Piston API...".to_string()
+            },
+            is_error: false
         });
 
         let (tx, rx) = mpsc::channel(32);
         self.output_rx = Some(rx);
-        
+        self.output_generation = self.generation;
+
         // Clone data for async task
         let code = self.code_text();
-        let problem = self.problem.clone();
+        let mut problem = self.problem.clone();
+        // Ad-hoc cases ride along after the built-ins
+        problem.test_cases.extend(self.custom_cases.iter().cloned());
+        if let Some(idx) = case_index {
+            if idx < problem.test_cases.len() {
+                problem.test_cases = vec![problem.test_cases[idx].clone()];
+            }
+        }
         let language = self.current_language;
-        
+
         // Spawn async execution
         tokio::spawn(async move {
             let results = run_tests_on_piston(code, problem, language, tx.clone()).await;
@@ -1173,32 +3037,120 @@ impl App {
         });
     }
 
-    fn run_code(&mut self) {
-        self.execute_code(false);  // false = run mode (inline results)
+    fn execute_code(&mut self, is_submit: bool) {
+        self.execute_code_with_cases(is_submit, None);
     }
 
-    fn move_to_line_start(&mut self) {
-        let (row, _) = self.editor.cursor();
-        self.editor.move_cursor(CursorMove::Jump(row as u16, 0));
+    /// When offline, show a clear banner instead of letting the request fail
+    /// with a cryptic error. Clears the flag so the next attempt retries.
+    fn blocked_while_offline(&mut self) -> bool {
+        if !self.offline {
+            return false;
+        }
+        self.show_output_panel = true;
+        self.execution_output.push(OutputLine {
+            text: "Offline — translation and submission unavailable. Press again to retry.".to_string(),
+            is_error: true,
+        });
+        self.offline = false;
+        true
     }
 
-    fn move_to_line_end(&mut self) {
-        let (row, _) = self.editor.cursor();
-        let line_len = self
-            .editor
-            .lines()
-            .get(row)
-            .map(|line| line.chars().count())
-            .unwrap_or(0);
-        self.editor
-            .move_cursor(CursorMove::Jump(row as u16, line_len as u16));
+    /// Per-frame-safe version of [`crate::problem::language_executable`],
+    /// using the provider availability cached at startup instead of
+    /// re-reading the key sources on every render
+    fn language_executable(&self, language: Language) -> bool {
+        language == Language::Python || self.translation_available
     }
 
-    fn delete_to_end_of_line(&mut self) {
-        self.editor.delete_line_by_end();
+    /// True when the current language cannot be executed at all (see
+    /// [`crate::problem::language_executable`]); a clear notice beats
+    /// letting the run come back all-failed with a cryptic error
+    fn blocked_by_language(&mut self) -> bool {
+        // Re-check the key sources here (not the cached flag): a key exported
+        // after launch should unblock without a restart
+        if crate::problem::language_executable(self.current_language) {
+            return false;
+        }
+        self.show_output_panel = true;
+        self.execution_output.push(OutputLine {
+            text: format!(
+                "⚠ run/submit unavailable in {} — no translation provider configured",
+                self.current_language.display_name()
+            ),
+            is_error: true,
+        });
+        true
     }
 
-    fn delete_to_start_of_line(&mut self) {
+    /// True while the post-429 cooldown is running; retrying during it
+    /// would only extend the per-IP ban window upstream
+    fn blocked_by_rate_limit(&mut self) -> bool {
+        let remaining = match self.piston_cooldown_remaining() {
+            Some(remaining) => remaining,
+            None => return false,
+        };
+        self.show_output_panel = true;
+        self.execution_output.push(OutputLine {
+            text: format!("Rate limited — retry in {}s", remaining.as_secs().max(1)),
+            is_error: true,
+        });
+        true
+    }
+
+    /// True while a Piston run is still in flight. Mashing run/submit would
+    /// otherwise spawn concurrent tasks and replace `output_rx`, orphaning
+    /// the earlier results; rejected presses get a brief notice instead.
+    fn execution_in_flight(&mut self) -> bool {
+        if self.output_rx.is_none() {
+            return false;
+        }
+        self.show_output_panel = true;
+        self.execution_output.push(OutputLine {
+            text: "Already running — waiting for the current run to finish...".to_string(),
+            is_error: false,
+        });
+        true
+    }
+
+    fn run_code(&mut self) {
+        if self.blocked_by_language() || self.blocked_by_rate_limit() || self.execution_in_flight() {
+            return;
+        }
+        self.execute_code(false);  // false = run mode (inline results)
+    }
+
+    /// Re-run a single test case, surfacing the result in the output panel
+    fn run_single_case(&mut self, case_index: usize) {
+        if self.blocked_by_language() || self.blocked_by_rate_limit() || self.execution_in_flight() {
+            return;
+        }
+        self.show_output_panel = true;
+        self.execute_code_with_cases(false, Some(case_index));
+    }
+
+    fn move_to_line_start(&mut self) {
+        let (row, _) = self.editor.cursor();
+        self.editor.move_cursor(CursorMove::Jump(row as u16, 0));
+    }
+
+    fn move_to_line_end(&mut self) {
+        let (row, _) = self.editor.cursor();
+        let line_len = self
+            .editor
+            .lines()
+            .get(row)
+            .map(|line| line.chars().count())
+            .unwrap_or(0);
+        self.editor
+            .move_cursor(CursorMove::Jump(row as u16, line_len as u16));
+    }
+
+    fn delete_to_end_of_line(&mut self) {
+        self.editor.delete_line_by_end();
+    }
+
+    fn delete_to_start_of_line(&mut self) {
         self.editor.delete_line_by_head();
     }
 
@@ -1210,7 +3162,7 @@ impl App {
         };
 
         let mut remove = 0usize;
-        for ch in line.chars().take(4) {
+        for ch in line.chars().take(self.tab_width) {
             if ch == ' ' {
                 remove += 1;
             } else if ch == '\t' {
@@ -1236,11 +3188,40 @@ impl App {
     }
 
     fn submit(&mut self) {
+        // Guard before touching state: entering Submitting with a stale run
+        // in flight would strand the screen on the wrong event kind
+        if self.blocked_while_offline()
+            || self.blocked_by_language()
+            || self.blocked_by_rate_limit()
+            || self.execution_in_flight()
+        {
+            return;
+        }
         self.state = AppState::Submitting(0.0, None);
         self.execute_code(true);
     }
 
     pub fn render(&mut self, frame: &mut Frame) {
+        // Below this the layout math produces zero-height areas (and the
+        // popup centering can panic), so bail out with a resize prompt
+        let size = frame.size();
+        if size.width < 40 || size.height < 15 {
+            let message = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Terminal too small",
+                    Style::default().fg(self.theme.warn).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    "please resize to at least 40x15",
+                    Style::default().fg(self.theme.text_dim),
+                )),
+            ])
+            .alignment(Alignment::Center);
+            frame.render_widget(message, size);
+            return;
+        }
+
         match &self.state {
             AppState::Coding => self.render_coding(frame),
             AppState::Countdown(count) => self.render_countdown(frame, *count),
@@ -1248,34 +3229,453 @@ impl App {
             AppState::Revealing(progress) => self.render_reveal(frame, *progress),
             AppState::Submitting(progress, results) => self.render_submitting(frame, *progress, results),
             AppState::Results(results) => self.render_results(frame, results),
+            AppState::Stats => self.render_stats(frame),
+            AppState::History => self.render_history(frame),
+            AppState::DebugView => self.render_debug_view(frame),
+            AppState::ProblemSelect => self.render_problem_select(frame),
+            AppState::ReviewTranslation => self.render_review_translation(frame),
+            AppState::SolutionView => self.render_solution_view(frame),
         }
+
+        self.render_toast(frame);
     }
     
+    /// Session summary: rounds played, languages the tower forced, pass rate
+    /// per language, and the fastest full-pass solve
+    fn render_stats(&self, frame: &mut Frame) {
+        let size = frame.size();
+        let gold = self.theme.gold;
+        let bronze = self.theme.bronze;
+        let purple = self.theme.purple;
+
+        let mut text = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("⧗ Rounds submitted: {}", self.stats.rounds),
+                Style::default().fg(self.theme.text),
+            )),
+            Line::from(""),
+        ];
+
+        // Languages in the order the tower forced them
+        let seen = if self.stats.languages_seen.is_empty() {
+            "none yet — the tower has been merciful".to_string()
+        } else {
+            self.stats
+                .languages_seen
+                .iter()
+                .map(|l| l.display_name())
+                .collect::<Vec<_>>()
+                .join(" → ")
+        };
+        text.push(Line::from(Span::styled(
+            "━━━ Languages endured ━━━",
+            Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+        )));
+        text.push(Line::from(Span::styled(seen, Style::default().fg(self.theme.text_dim))));
+        text.push(Line::from(""));
+
+        text.push(Line::from(Span::styled(
+            "━━━ Accuracy per language ━━━",
+            Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+        )));
+        if self.stats.per_language.is_empty() {
+            text.push(Line::from(Span::styled(
+                "no submissions yet",
+                Style::default().fg(self.theme.text_faint),
+            )));
+        }
+        for (language, passed, total) in &self.stats.per_language {
+            let percent = (*passed * 100) / (*total).max(1);
+            let color = if percent == 100 {
+                self.theme.success
+            } else if percent >= 50 {
+                self.theme.warn
+            } else {
+                self.theme.error
+            };
+            text.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", language.display_name()),
+                    Style::default().fg(self.theme.text),
+                ),
+                Span::styled(
+                    format!("{}/{} cases ({}%)", passed, total, percent),
+                    Style::default().fg(color),
+                ),
+            ]));
+        }
+        text.push(Line::from(""));
+
+        if let Some(fastest) = self.stats.fastest_solve {
+            text.push(Line::from(vec![
+                Span::styled("◈ Fastest solve: ", Style::default().fg(gold)),
+                Span::styled(
+                    format!("{:.1}s", fastest.as_secs_f32()),
+                    Style::default().fg(gold).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            text.push(Line::from(""));
+        }
+
+        text.push(Line::from(Span::styled(
+            "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+            Style::default().fg(bronze),
+        )));
+        text.push(Line::from(vec![
+            Span::styled("Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" to go back", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        let popup_area = centered_rect(60, 70, size);
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ CHRONICLE OF THE CLIMB ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Read-only preview of the pending translation, shown between reveal
+    /// and apply when `BABEL_REVIEW_TRANSLATIONS=1`
+    fn render_review_translation(&mut self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+        let purple = self.theme.purple;
+
+        let translated = match &self.pending_translation {
+            Some(TranslationEvent::Success(code)) => code.clone(),
+            // Nothing to review (translation was dropped) — skip the frame;
+            // tick() notices the same condition and completes the transition
+            _ => return,
+        };
+        let lang_name = self
+            .pending_language
+            .map(|lang| lang.display_name())
+            .unwrap_or("?");
+
+        let popup_area = centered_rect(80, 80, size);
+        let visible = popup_area.height.saturating_sub(4) as usize;
+        let code_lines: Vec<&str> = translated.lines().collect();
+        let max_scroll = code_lines.len().saturating_sub(visible);
+        if self.review_scroll > max_scroll {
+            self.review_scroll = max_scroll;
+        }
+
+        let mut text = vec![
+            Line::from(Span::styled(
+                format!("── Translated to {} ──", lang_name),
+                Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+            )),
+        ];
+        for line in code_lines.iter().skip(self.review_scroll).take(visible.max(1)) {
+            text.push(Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(self.theme.text),
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" accept  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" keep old code  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("↑/↓", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ REVIEW THE TRANSLATION ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Pick a specific problem instead of relying on Ctrl+R's randomness
+    fn render_problem_select(&mut self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+        let purple = self.theme.purple;
+
+        // Coding screen stays visible underneath the picker
+        self.render_coding(frame);
+
+        let mut text = vec![Line::from("")];
+        for (i, problem) in Problem::all().iter().enumerate() {
+            let selected = i == self.problem_select_index;
+            let marker = if selected { "▶ " } else { "  " };
+            let style = if selected {
+                Style::default().fg(gold).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            text.push(Line::from(Span::styled(
+                format!("{}{}", marker, problem.title),
+                style,
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+            Style::default().fg(bronze),
+        )));
+        text.push(Line::from(vec![
+            Span::styled("↑/↓", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" choose  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Enter", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" load  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" cancel", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        let popup_area = centered_rect(50, 50, size);
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ CHOOSE YOUR TRIAL ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Scrollable view of the exact harness sent to Piston and the raw
+    /// response — the in-app equivalent of piston_full.log
+    fn render_debug_view(&mut self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+
+        let (harness, raw_response) = match &self.debug_info {
+            Some(info) => info.clone(),
+            None => return,
+        };
+
+        let mut all_lines: Vec<Line> = Vec::new();
+        all_lines.push(Line::from(Span::styled(
+            "── Generated harness ──",
+            Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+        )));
+        for line in harness.lines() {
+            all_lines.push(Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(self.theme.text_dim),
+            )));
+        }
+        all_lines.push(Line::from(""));
+        all_lines.push(Line::from(Span::styled(
+            "── Raw response ──",
+            Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+        )));
+        for line in raw_response.lines() {
+            all_lines.push(Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(self.theme.text),
+            )));
+        }
+
+        let popup_area = centered_rect(85, 85, size);
+        let visible = popup_area.height.saturating_sub(3) as usize;
+        let max_scroll = all_lines.len().saturating_sub(visible);
+        if self.debug_scroll > max_scroll {
+            self.debug_scroll = max_scroll;
+        }
+
+        let mut text: Vec<Line> = all_lines
+            .into_iter()
+            .skip(self.debug_scroll)
+            .take(visible.max(1))
+            .collect();
+        text.push(Line::from(vec![
+            Span::styled("↑/↓ PgUp/PgDn", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" back", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ LAST RUN — VERBOSE ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Read-only reference solution overlay (Python), offered on the results
+    /// screen after repeated failed submissions
+    fn render_solution_view(&mut self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+
+        let solution = match &self.problem.reference_solution {
+            Some(code) => code.clone(),
+            None => return,
+        };
+
+        let mut all_lines: Vec<Line> = Vec::new();
+        all_lines.push(Line::from(Span::styled(
+            format!("── {} — reference solution (Python) ──", self.problem.title),
+            Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+        )));
+        all_lines.push(Line::from(""));
+        for line in solution.lines() {
+            all_lines.push(Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(self.theme.text),
+            )));
+        }
+
+        let popup_area = centered_rect(70, 70, size);
+        let visible = popup_area.height.saturating_sub(3) as usize;
+        let max_scroll = all_lines.len().saturating_sub(visible);
+        if self.solution_scroll > max_scroll {
+            self.solution_scroll = max_scroll;
+        }
+
+        let mut text: Vec<Line> = all_lines
+            .into_iter()
+            .skip(self.solution_scroll)
+            .take(visible.max(1))
+            .collect();
+        text.push(Line::from(vec![
+            Span::styled("↑/↓ PgUp/PgDn", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" back", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ REFERENCE SOLUTION ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Scrollable list of this session's submissions, newest first
+    fn render_history(&self, frame: &mut Frame) {
+        let size = frame.size();
+        let gold = self.theme.gold;
+        let bronze = self.theme.bronze;
+
+        let popup_area = centered_rect(70, 70, size);
+        let visible = popup_area.height.saturating_sub(5) as usize;
+
+        let mut text = vec![Line::from("")];
+
+        if self.history.is_empty() {
+            text.push(Line::from(Span::styled(
+                "no submissions this session",
+                Style::default().fg(self.theme.text_faint),
+            )));
+        }
+
+        for record in self.history.iter().rev().skip(self.history_scroll).take(visible.max(1)) {
+            let percent = (record.passed * 100) / record.total.max(1);
+            let score_color = if percent == 100 {
+                self.theme.success
+            } else if percent >= 50 {
+                self.theme.warn
+            } else {
+                self.theme.error
+            };
+            text.push(Line::from(vec![
+                Span::styled(
+                    record.at.format("%H:%M:%S ").to_string(),
+                    Style::default().fg(self.theme.text_faint),
+                ),
+                Span::styled(
+                    format!("{:<12}", record.language.display_name()),
+                    Style::default().fg(self.theme.purple),
+                ),
+                Span::styled(
+                    format!("{:<20}", record.problem),
+                    Style::default().fg(self.theme.text),
+                ),
+                Span::styled(
+                    format!("{}/{} ({}%)", record.passed, record.total, percent),
+                    Style::default().fg(score_color).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+        }
+
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("↑/↓", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" back", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ SCROLLS OF ATTEMPTS ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
     fn render_submitting(&self, frame: &mut Frame, progress: f32, results: &Option<TestResults>) {
         let size = frame.size();
         let area = centered_rect(70, 25, size);
 
         // Theme colors
-        let gold = Color::Rgb(255, 191, 0);
-        let bronze = Color::Rgb(139, 90, 43);
-        let purple = Color::Rgb(147, 112, 219);
+        let gold = self.theme.gold;
+        let bronze = self.theme.bronze;
+        let purple = self.theme.purple;
 
         let percent_val = (progress * 100.0) as u16;
         
         // Determine phase and color based on progress and results
         let (bar_color, loading_text) = if let Some(results) = results {
             // Revealing results phase (95-100%)
-            let score_percent = (results.passed as f32 / results.total as f32 * 100.0) as u8;
+            let score_percent = results.score_percent();
             let color = if score_percent == 100 {
                 gold
             } else if score_percent >= 80 {
-                Color::Rgb(100, 200, 130)
+                self.theme.success
             } else if score_percent >= 50 {
-                Color::Rgb(255, 200, 80)
+                self.theme.warn
             } else {
-                Color::Rgb(255, 100, 100)
+                self.theme.error
             };
-            
+
             let texts = if score_percent == 100 {
                 vec![
                     "The tower acknowledges your mastery...",
@@ -1383,7 +3783,7 @@ impl App {
                         if is_filled {
                             spans.push(Span::styled(ch.to_string(), Style::default().fg(Color::Black).bg(bar_color)));
                         } else {
-                            spans.push(Span::styled(ch.to_string(), Style::default().fg(Color::Rgb(180, 180, 180))));
+                            spans.push(Span::styled(ch.to_string(), Style::default().fg(self.theme.text_dim)));
                         }
                     } else {
                         if is_filled {
@@ -1409,6 +3809,30 @@ impl App {
         
         let paragraph = Paragraph::new(content);
         frame.render_widget(paragraph, inner);
+
+        // Typing is ignored on this screen; say so, and offer the one key
+        // that does work while results are still pending
+        if area.y + area.height < size.height {
+            let hint_area = Rect {
+                x: area.x,
+                y: area.y + area.height,
+                width: area.width,
+                height: 1,
+            };
+            let hint = if results.is_none() {
+                Line::from(vec![
+                    Span::styled("Submitting — please wait  ┃  ", Style::default().fg(self.theme.text_faint)),
+                    Span::styled("Esc", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+                    Span::styled(" cancel", Style::default().fg(self.theme.text_faint)),
+                ])
+            } else {
+                Line::from(Span::styled(
+                    "Submitting — please wait",
+                    Style::default().fg(self.theme.text_faint),
+                ))
+            };
+            frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), hint_area);
+        }
     }
 
 
@@ -1422,7 +3846,7 @@ impl App {
                 .constraints([
                     Constraint::Length(3),   // Header
                     Constraint::Min(10),     // Content (problem + editor)
-                    Constraint::Length(12),  // Output panel
+                    Constraint::Length(self.output_panel_height), // Output panel
                     Constraint::Length(2),   // Footer
                 ])
                 .split(size)
@@ -1440,40 +3864,351 @@ impl App {
         // Header with arcade styling
         self.render_header(frame, main_chunks[0]);
 
-        // Split content: 1/3 problem, 2/3 editor
-        let content_area = if self.show_output_panel { main_chunks[1] } else { main_chunks[1] };
-        let content_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(33),
-                Constraint::Percentage(67),
-            ])
-            .split(content_area);
+        // Split content: 1/3 problem, 2/3 editor — the editor gives up a
+        // quarter when the last-results sidebar is docked
+        let content_area = if self.show_output_panel { main_chunks[1] } else { main_chunks[1] };
+        let sidebar_docked = self.show_results_sidebar && self.sidebar_results.is_some();
+        let content_chunks = if sidebar_docked {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(28),
+                    Constraint::Percentage(47),
+                    Constraint::Percentage(25),
+                ])
+                .split(content_area)
+        } else {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(33),
+                    Constraint::Percentage(67),
+                ])
+                .split(content_area)
+        };
+
+        // Store editor area for mouse clicks
+        self.editor_area = content_chunks[1];
+
+        // Render problem description
+        self.render_problem(frame, content_chunks[0]);
+
+        // Render code editor
+        self.render_editor(frame, content_chunks[1]);
+
+        if sidebar_docked {
+            self.render_results_sidebar(frame, content_chunks[2]);
+        }
+
+        // Render output panel if visible
+        if self.show_output_panel {
+            self.render_output_panel(frame, main_chunks[2]);
+        }
+
+        // Footer with timer
+        let footer_idx = if self.show_output_panel { 3 } else { 2 };
+        self.render_footer(frame, main_chunks[footer_idx]);
+
+        if self.tip_shown_at.is_some() {
+            self.render_language_tip(frame);
+        }
+
+        if self.show_source_picker {
+            self.render_source_picker(frame);
+        }
+
+        if self.case_entry.is_some() {
+            self.render_case_entry(frame);
+        }
+
+        if self.show_hints_overlay {
+            self.render_hints_overlay(frame);
+        }
+    }
+
+    /// Info card shown for a few seconds after each reveal: one line of
+    /// survival advice for the language the tower just imposed
+    fn render_language_tip(&self, frame: &mut Frame) {
+        let size = frame.size();
+        let message = format!(
+            "⟡ {}: {}",
+            self.current_language.display_name(),
+            self.current_language.tip()
+        );
+        let card_width = (message.chars().count() as u16 + 4).min(size.width);
+        // One row below the toast position so the two can coexist
+        let card_area = Rect {
+            x: size.width.saturating_sub(card_width) / 2,
+            y: 6,
+            width: card_width,
+            height: 3,
+        };
+        frame.render_widget(Clear, card_area);
+        let card = Paragraph::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(self.theme.amber),
+        )))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.bronze))
+                .style(Style::default().bg(Color::Black)),
+        );
+        frame.render_widget(card, card_area);
+    }
+
+    /// Progressive hints popup (Ctrl+H): revealed hints so far, one per press
+    /// Ad-hoc test-case entry overlay (Ctrl+J): prompts for each parameter
+    /// in turn, then the expected output
+    fn render_case_entry(&self, frame: &mut Frame) {
+        let entry = match &self.case_entry {
+            Some(entry) => entry,
+            None => return,
+        };
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+
+        let mut text = vec![Line::from("")];
+        for (param, value) in self.problem.parameters.iter().zip(&entry.inputs) {
+            text.push(Line::from(vec![
+                Span::styled(
+                    format!(" {} = ", param.name),
+                    Style::default().fg(self.theme.text_dim),
+                ),
+                Span::styled(value.clone(), Style::default().fg(self.theme.text)),
+            ]));
+        }
+        let prompt = if entry.expecting_output {
+            " expected output".to_string()
+        } else {
+            match self.problem.parameters.get(entry.inputs.len()) {
+                Some(param) => format!(" {} ({})", param.name, param.param_type),
+                None => " expected output".to_string(),
+            }
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("{} = ", prompt),
+                Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(entry.buffer.clone(), Style::default().fg(self.theme.text)),
+            Span::styled("▌", Style::default().fg(gold)),
+        ]));
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                if entry.expecting_output { " add case  ┃  " } else { " next field  ┃  " },
+                Style::default().fg(self.theme.text_faint),
+            ),
+            Span::styled("Esc", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" cancel", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        let popup_area = centered_rect(60, 35, size);
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ ADD TEST CASE ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
+
+    /// Docked, dimmed copy of the last submission's scoreboard, kept beside
+    /// the editor for the fix-and-compare loop (`c` on the results screen)
+    fn render_results_sidebar(&self, frame: &mut Frame, area: Rect) {
+        let results = match &self.sidebar_results {
+            Some(results) => results,
+            None => return,
+        };
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" {} / {} passed", results.passed, results.total),
+                Style::default().fg(self.theme.text_dim).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for result in &results.details {
+            // Same accessible-label convention as the results scoreboard
+            let (symbol, color) = if self.accessible_labels {
+                if result.passed {
+                    ("✓ PASS", self.theme.text_dim)
+                } else {
+                    ("✗ FAIL", self.theme.amber)
+                }
+            } else if result.passed {
+                ("◆", self.theme.text_dim)
+            } else {
+                ("◇", self.theme.amber)
+            };
+            lines.push(Line::from(Span::styled(
+                format!(" {} Case {}", symbol, result.case_number),
+                Style::default().fg(color),
+            )));
+            if !result.passed {
+                lines.push(Line::from(Span::styled(
+                    format!("   got {}", result.actual),
+                    Style::default().fg(self.theme.text_faint),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("^G", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" hide", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.bronze))
+            .title(Span::styled(
+                " ◆ LAST RUN ◆ ",
+                Style::default().fg(self.theme.text_dim).add_modifier(Modifier::BOLD),
+            ));
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Which language was the externally loaded solution written in?
+    /// Opens after a Ctrl+O paste with the guessed language preselected.
+    fn render_source_picker(&self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+        let purple = self.theme.purple;
+
+        let mut text = vec![Line::from("")];
+        for (i, language) in Language::all().iter().enumerate() {
+            let selected = i == self.source_picker_index;
+            let marker = if selected { "▶ " } else { "  " };
+            let style = if selected {
+                Style::default().fg(gold).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            text.push(Line::from(Span::styled(
+                format!("{}{}", marker, language.display_name()),
+                style,
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("↑/↓", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" choose  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Enter", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" confirm  ┃  ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Esc", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" skip", Style::default().fg(self.theme.text_faint)),
+        ]));
+
+        let popup_area = centered_rect(40, 70, size);
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ SOURCE LANGUAGE ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
+
+        frame.render_widget(popup, popup_area);
+    }
 
-        // Store editor area for mouse clicks
-        self.editor_area = content_chunks[1];
+    fn render_hints_overlay(&self, frame: &mut Frame) {
+        let size = frame.size();
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
+
+        let revealed = self.hints_revealed.min(self.problem.hints.len());
+        let mut text = vec![Line::from("")];
+        for (idx, hint) in self.problem.hints.iter().take(revealed).enumerate() {
+            text.push(Line::from(vec![
+                Span::styled(
+                    format!(" {}. ", idx + 1),
+                    Style::default().fg(self.theme.amber).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(hint.clone(), Style::default().fg(self.theme.text)),
+            ]));
+            text.push(Line::from(""));
+        }
+        let remaining = self.problem.hints.len() - revealed;
+        if remaining > 0 {
+            text.push(Line::from(Span::styled(
+                format!(" {} more hint(s) sealed...", remaining),
+                Style::default().fg(self.theme.text_faint),
+            )));
+            text.push(Line::from(""));
+        }
+        text.push(Line::from(vec![
+            Span::styled("^H", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                if remaining > 0 { " next hint  ┃  " } else { " (no more)  ┃  " },
+                Style::default().fg(self.theme.text_faint),
+            ),
+            Span::styled("Esc", Style::default().fg(self.theme.purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" close", Style::default().fg(self.theme.text_faint)),
+        ]));
 
-        // Render problem description
-        self.render_problem(frame, content_chunks[0]);
+        let popup_area = centered_rect(60, 40, size);
+        frame.render_widget(Clear, popup_area);
+        let popup = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().bg(Color::Black))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(bronze))
+                .title(Span::styled(" ◆ HINTS ◆ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+                .style(Style::default().bg(Color::Black)));
 
-        // Render code editor
-        self.render_editor(frame, content_chunks[1]);
+        frame.render_widget(popup, popup_area);
+    }
 
-        // Render output panel if visible
-        if self.show_output_panel {
-            self.render_output_panel(frame, main_chunks[2]);
+    /// Toast notifications (mastery, export confirmations) float over
+    /// whatever screen is active, top center
+    fn render_toast(&self, frame: &mut Frame) {
+        let size = frame.size();
+        if let Some((message, _)) = &self.toast {
+            let toast_width = (message.chars().count() as u16 + 4).min(size.width);
+            let toast_area = Rect {
+                x: size.width.saturating_sub(toast_width) / 2,
+                y: 3,
+                width: toast_width,
+                height: 3,
+            };
+            frame.render_widget(Clear, toast_area);
+            let toast = Paragraph::new(Line::from(Span::styled(
+                message.clone(),
+                Style::default().fg(self.theme.gold).add_modifier(Modifier::BOLD),
+            )))
+            .alignment(Alignment::Center)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.gold))
+                .style(Style::default().bg(Color::Black)));
+            frame.render_widget(toast, toast_area);
         }
-
-        // Footer with timer
-        let footer_idx = if self.show_output_panel { 3 } else { 2 };
-        self.render_footer(frame, main_chunks[footer_idx]);
     }
 
     fn render_header(&self, frame: &mut Frame, area: Rect) {
         // Terminal of Babel - mystical ancient tower meets cyberpunk terminal
-        let border_color = Color::Rgb(139, 90, 43);  // Bronze/amber border
-        let title_color = Color::Rgb(255, 191, 0);   // Gold
-        let accent_color = Color::Rgb(147, 112, 219); // Medium purple
+        let border_color = self.theme.bronze;
+        let title_color = self.theme.gold;
+        let accent_color = self.theme.purple;
 
         let title = vec![
             Span::styled("┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓", Style::default().fg(border_color)),
@@ -1481,7 +4216,7 @@ impl App {
             Span::styled("┃ ", Style::default().fg(border_color)),
             Span::styled("◈ ", Style::default().fg(accent_color)),
             Span::styled("TERMINAL ", Style::default().fg(title_color).add_modifier(Modifier::BOLD)),
-            Span::styled("of ", Style::default().fg(Color::Rgb(180, 180, 180))),
+            Span::styled("of ", Style::default().fg(self.theme.text_dim)),
             Span::styled("BABEL", Style::default().fg(title_color).add_modifier(Modifier::BOLD)),
             Span::styled(" ◈", Style::default().fg(accent_color)),
             Span::styled(" ┃", Style::default().fg(border_color)),
@@ -1495,10 +4230,15 @@ impl App {
         frame.render_widget(header, area);
     }
 
-    fn render_problem(&self, frame: &mut Frame, area: Rect) {
-        let title_color = Color::Rgb(255, 191, 0);   // Gold
-        let border_color = Color::Rgb(139, 90, 43);  // Bronze
-        let label_color = Color::Rgb(180, 140, 80);  // Warm amber
+    fn render_problem(&mut self, frame: &mut Frame, area: Rect) {
+        let title_color = self.theme.gold;
+        // Highlight the border when this panel has focus
+        let border_color = if self.focus == Focus::Problem {
+            self.theme.gold
+        } else {
+            self.theme.bronze
+        };
+        let label_color = self.theme.amber;
 
         let mut text = vec![
             Line::from(vec![
@@ -1510,20 +4250,49 @@ impl App {
         ];
 
         for line in self.problem.description.lines() {
-            text.push(Line::from(Span::styled(line, Style::default().fg(Color::Rgb(220, 220, 220)))));
+            text.push(Line::from(Span::styled(line, Style::default().fg(self.theme.text))));
         }
 
         text.push(Line::from(""));
         text.push(Line::from(Span::styled("━━━ Examples", Style::default().fg(label_color).add_modifier(Modifier::BOLD))));
         text.push(Line::from(""));
 
-        for example in &self.problem.examples {
-            for line in example.lines() {
-                text.push(Line::from(Span::styled(line, Style::default().fg(Color::Rgb(160, 160, 160)))));
+        if self.show_examples {
+            for example in &self.problem.examples {
+                for line in example.lines() {
+                    text.push(Line::from(Span::styled(line, Style::default().fg(self.theme.text_dim))));
+                }
+                text.push(Line::from(""));
             }
+        } else {
+            // Collapsed on purpose — don't leak the example outputs
+            text.push(Line::from(Span::styled(
+                "[examples hidden — press e]",
+                Style::default().fg(self.theme.text_faint),
+            )));
             text.push(Line::from(""));
         }
 
+        text.push(Line::from(Span::styled("━━━ Constraints", Style::default().fg(label_color).add_modifier(Modifier::BOLD))));
+        text.push(Line::from(""));
+        if self.show_constraints {
+            for constraint in &self.problem.constraints {
+                text.push(Line::from(Span::styled(format!("• {}", constraint), Style::default().fg(self.theme.text_dim))));
+            }
+        } else {
+            text.push(Line::from(Span::styled(
+                "[constraints hidden — press c]",
+                Style::default().fg(self.theme.text_faint),
+            )));
+        }
+
+        // Clamp scrolling so the panel can't run past the content
+        let visible_height = area.height.saturating_sub(2) as usize;
+        let max_scroll = text.len().saturating_sub(visible_height);
+        if self.problem_scroll > max_scroll {
+            self.problem_scroll = max_scroll;
+        }
+
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color))
@@ -1531,7 +4300,8 @@ impl App {
 
         let paragraph = Paragraph::new(text)
             .block(block)
-            .wrap(Wrap { trim: false });
+            .wrap(Wrap { trim: false })
+            .scroll((self.problem_scroll as u16, 0));
 
         frame.render_widget(paragraph, area);
     }
@@ -1565,7 +4335,7 @@ impl App {
             let line_num = format!("{:>width$} ", idx + 1, width = line_number_width);
             let mut spans = vec![Span::styled(line_num, Style::default().fg(Color::DarkGray))];
 
-            let mut highlighted = SyntectHighlighter::highlight(line, &self.current_language);
+            let mut highlighted = self.highlight_cache.spans(line, self.current_language);
             if highlighted.is_empty() {
                 highlighted.push(Span::raw(String::new()));
             }
@@ -1632,12 +4402,26 @@ impl App {
             rendered_lines.push(Line::from(spans));
         }
 
-        let title = format!(" ◇ {} ", self.current_language.display_name());
-        let panel_color = Color::Rgb(147, 112, 219); // Medium purple - matches header accent
+        // Flag languages that can't execute in this session up front, instead
+        // of letting run/submit fail after the fact
+        let title = if self.language_executable(self.current_language) {
+            format!(" ◇ {} ", self.current_language.display_name())
+        } else {
+            format!(
+                " ◇ {} ⚠ run/submit unavailable ",
+                self.current_language.display_name()
+            )
+        };
+        // Accent color when focused - matches header; dimmed otherwise
+        let panel_color = if self.focus == Focus::Editor {
+            self.theme.purple
+        } else {
+            self.theme.purple_dim
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(panel_color))
-            .title(Span::styled(title, Style::default().fg(Color::Rgb(255, 191, 0)).add_modifier(Modifier::BOLD)));
+            .title(Span::styled(title, Style::default().fg(self.theme.gold).add_modifier(Modifier::BOLD)));
 
         let paragraph = Paragraph::new(rendered_lines)
             .block(block)
@@ -1647,8 +4431,8 @@ impl App {
     }
 
     fn render_output_panel(&self, frame: &mut Frame, area: Rect) {
-        let bronze = Color::Rgb(139, 90, 43);
-        let gold = Color::Rgb(255, 191, 0);
+        let bronze = self.theme.bronze;
+        let gold = self.theme.gold;
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -1658,46 +4442,75 @@ impl App {
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
-        let lines: Vec<Line> = self.execution_output.iter().map(|line| {
+        // Truncated by default: one line stays one rendered row, so the
+        // vertical scroll offset keeps meaning "lines", not "rows". Ctrl+L
+        // opts back into wrapping.
+        let width = inner_area.width as usize;
+        let mut lines: Vec<Line> = Vec::with_capacity(self.execution_output.len() + 1);
+        // Capped output gets a tally row of its own instead of overwriting
+        // the oldest surviving line
+        if self.output_lines_dropped > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("... {} earlier lines dropped ...", self.output_lines_dropped),
+                Style::default().fg(self.theme.text_faint),
+            )));
+        }
+        lines.extend(self.execution_output.iter().map(|line| {
+            let text = if self.wrap_output {
+                line.text.clone()
+            } else {
+                truncate_with_ellipsis(&line.text, width)
+            };
             Line::from(Span::styled(
-                &line.text,
+                text,
                 if line.is_error {
-                    Style::default().fg(Color::Rgb(255, 100, 100))
+                    Style::default().fg(self.theme.error)
                 } else {
-                    Style::default().fg(Color::Rgb(200, 200, 200))
+                    Style::default().fg(self.theme.text)
                 }
             ))
-        }).collect();
+        }));
 
-        let paragraph = Paragraph::new(lines)
-            .wrap(Wrap { trim: false })
+        let mut paragraph = Paragraph::new(lines)
             .scroll((self.scroll_offset as u16, 0));
+        if self.wrap_output {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
 
         frame.render_widget(paragraph, inner_area);
     }
 
-    fn render_footer(&self, frame: &mut Frame, area: Rect) {
-        let elapsed = self.last_randomize.elapsed();
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let elapsed = self.elapsed_since(self.last_randomize);
         let remaining = self.randomize_interval.saturating_sub(elapsed);
         let secs = remaining.as_secs();
 
         // Theme colors
-        let gold = Color::Rgb(255, 191, 0);
-        let purple = Color::Rgb(147, 112, 219);
-        let bronze = Color::Rgb(139, 90, 43);
-        let text_dim = Color::Rgb(140, 140, 140);
+        let gold = self.theme.gold;
+        let purple = self.theme.purple;
+        let bronze = self.theme.bronze;
+        let text_dim = self.theme.text_faint;
 
         let timer_color = if secs < 10 {
-            Color::Rgb(255, 80, 80)  // Soft red
+            self.theme.error
         } else if secs < 20 {
-            Color::Rgb(255, 200, 80) // Warm yellow
+            self.theme.warn
         } else {
-            Color::Rgb(100, 200, 130) // Soft green
+            self.theme.success
         };
 
-        let mut footer_spans = vec![
-            Span::styled("⧗ ", Style::default().fg(bronze)),
-            Span::styled(format!("{}s", secs), Style::default().fg(timer_color).add_modifier(Modifier::BOLD)),
+        let mut footer_spans = if self.current_language.has_alternate() {
+            vec![
+                Span::styled("⧗ ", Style::default().fg(bronze)),
+                Span::styled(format!("{}s", secs), Style::default().fg(timer_color).add_modifier(Modifier::BOLD)),
+            ]
+        } else {
+            vec![
+                Span::styled("◈ ", Style::default().fg(bronze)),
+                Span::styled("Fixed language", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            ]
+        };
+        footer_spans.extend(vec![
             Span::styled(" ┃ ", Style::default().fg(bronze)),
             Span::styled("^S", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
             Span::styled(" Submit ", Style::default().fg(text_dim)),
@@ -1705,15 +4518,69 @@ impl App {
             Span::styled(" New ", Style::default().fg(text_dim)),
             Span::styled("^C", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
             Span::styled(" Run ", Style::default().fg(text_dim)),
-            Span::styled("^Q", Style::default().fg(Color::Rgb(180, 80, 80)).add_modifier(Modifier::BOLD)),
+            Span::styled("^T", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" Swap ", Style::default().fg(text_dim)),
+            Span::styled("^B", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" Focus ", Style::default().fg(text_dim)),
+            Span::styled("^Q", Style::default().fg(self.theme.error).add_modifier(Modifier::BOLD)),
             Span::styled(" Quit", Style::default().fg(text_dim)),
-        ];
+        ]);
+
+        // Live cursor position, handy for matching error line numbers from
+        // the output panel
+        let (row, col) = self.editor.cursor();
+        footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
+        footer_spans.push(Span::styled(
+            format!("Ln {}, Col {} / {}", row + 1, col + 1, self.editor.lines().len()),
+            Style::default().fg(text_dim),
+        ));
+
+        if let Some(remaining) = self.translation_cooldown_remaining() {
+            footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
+            footer_spans.push(Span::styled(
+                format!("⏳ rate-limited — swaps paused {}s", remaining.as_secs() + 1),
+                Style::default().fg(self.theme.warn),
+            ));
+        }
+
+        // Background translation still running (e.g. a retry finished after
+        // the reveal) — show it's working, not frozen
+        if self.translation_in_flight() {
+            footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
+            footer_spans.push(Span::styled(
+                format!("{} translating", self.spinner_glyph()),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+
+        if self.offline {
+            footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
+            footer_spans.push(Span::styled(
+                "⚠ Offline — translation and submission unavailable",
+                Style::default().fg(self.theme.error),
+            ));
+        }
+
+        if !self.translation_available {
+            footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
+            footer_spans.push(Span::styled(
+                "⚠ no API key — translation disabled",
+                Style::default().fg(self.theme.warn),
+            ));
+        }
 
         if !self.show_output_panel {
             footer_spans.push(Span::styled(" ┃ ", Style::default().fg(bronze)));
             footer_spans.push(Span::styled("Output hidden", Style::default().fg(Color::Rgb(100, 100, 100))));
         }
 
+        // Hit-boxes for the clickable labels, derived from the span contents
+        // (each shortcut span plus its caption) so reordering the footer —
+        // or the shorter fixed-language prefix — can't silently shift which
+        // label fires which action
+        self.footer_area = area;
+        self.footer_buttons = footer_hit_boxes(&footer_spans, area);
+
         let footer = Paragraph::new(Line::from(footer_spans))
             .alignment(Alignment::Center);
 
@@ -1726,25 +4593,17 @@ impl App {
         // First render the normal coding view so user can see their code
         self.render_coding(frame);
         
-        // Then overlay the big countdown
+        // Then overlay the big countdown - color shifts as time runs out,
+        // independent of the configured COUNTDOWN_SECS
         let color = match count {
-            5 => Color::Green,
-            4 => Color::Yellow,
-            3 => Color::Yellow,
-            2 => Color::Rgb(255, 165, 0), // Orange
             1 => Color::Red,
-            _ => Color::White,
+            2 => Color::Rgb(255, 165, 0), // Orange
+            3 | 4 => Color::Yellow,
+            _ => Color::Green,
         };
 
         // Big ASCII art numbers using the standardized function
-        let big_number = match count {
-            5 => self.get_ascii_number(5),
-            4 => self.get_ascii_number(4),
-            3 => self.get_ascii_number(3),
-            2 => self.get_ascii_number(2),
-            1 => self.get_ascii_number(1),
-            _ => self.get_ascii_number(0),
-        };
+        let big_number = self.get_ascii_number(count.min(9));
 
         let popup_area = centered_rect(50, 36, size);
         let popup_height = popup_area.height as usize;
@@ -1809,93 +4668,97 @@ impl App {
         frame.render_widget(popup, popup_area);
     }
 
-    fn render_reveal(&self, frame: &mut Frame, progress: f32) {
+    /// Reduced-motion background: a uniform `░` fill whose brightness fades
+    /// with `fade` (0.0 = black, 1.0 = full). No RNG, no flicker.
+    fn render_calm_fill(&self, frame: &mut Frame, fade: f32) {
         let size = frame.size();
-        
+        let level = (40.0 + fade * 60.0) as u8;
+        let color = Color::Rgb(level, level.saturating_sub(10), level + 20);
+        let row = "░".repeat(size.width as usize);
+        let lines: Vec<Line> = (0..size.height)
+            .map(|_| Line::from(Span::styled(row.clone(), Style::default().fg(color))))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), size);
+    }
+
+    fn render_reveal(&mut self, frame: &mut Frame, progress: f32) {
+        let size = frame.size();
+
         // Get the pending language name
         let lang_name = self.pending_language
             .as_ref()
             .map(|l| l.display_name())
             .unwrap_or("???");
-        
+
+        if self.reduced_motion {
+            // Calm fade-out instead of the glitch field
+            self.render_calm_fill(frame, 1.0 - progress * 0.5);
+            self.render_reveal_overlay(frame, progress, lang_name);
+            return;
+        }
+
         // Create glitch effect background (same as transition)
         let glitch_chars = ["█", "▓", "▒", "░", "▄", "▀", "▌", "▐"];
-        let mut bg_lines = Vec::new();
         let char_idx = (self.glitch_frame % glitch_chars.len()) as usize;
-        
+
         let height = size.height as usize;
         let width = size.width as usize;
-        
+
         // Use a decreasing glitch intensity as reveal progresses
         let glitch_intensity = 0.8 - (progress * 0.5);
-        
+
+        self.glitch.ensure_size(width, height);
+        self.glitch.advance();
+        self.glitch.fill_rows(glitch_chars[char_idx], |_, _| glitch_intensity);
+
+        let glitch = &self.glitch;
+        let glitch_frame = self.glitch_frame;
+        let mut bg_lines = Vec::with_capacity(height);
+
         for i in 0..height {
             let intensity = ((i as f32 / height as f32) - 0.5).abs();
             let wave = (i as f32 * 0.1 + progress * 10.0).sin();
-            let phase = (self.glitch_frame as f32 * 0.1 + i as f32 * 0.05).sin();
-            
+            let phase = (glitch_frame as f32 * 0.1 + i as f32 * 0.05).sin();
+
             // Generate random rainbow colors - full spectrum
-            let hue_base = (i as f32 * 7.0 + self.glitch_frame as f32 * 3.0) % 360.0;
+            let hue_base = (i as f32 * 7.0 + glitch_frame as f32 * 3.0) % 360.0;
             let hue_offset = wave * 60.0 + phase * 40.0;
             let hue = (hue_base + hue_offset).rem_euclid(360.0);
-            
+
             // Vary saturation and brightness based on intensity
+            let jitter = glitch.jitter(i);
             let saturation = if intensity < 0.1 {
-                0.9 + rand::random::<f32>() * 0.1  // Very saturated near progress
+                0.9 + jitter * 0.1  // Very saturated near progress
             } else if intensity < 0.3 {
-                0.6 + rand::random::<f32>() * 0.3  // Medium saturation
+                0.6 + jitter * 0.3  // Medium saturation
             } else {
-                0.3 + rand::random::<f32>() * 0.4  // Lower saturation
+                0.3 + jitter * 0.4  // Lower saturation
             };
-            
+
             let brightness = if intensity < 0.1 {
-                0.8 + rand::random::<f32>() * 0.2  // Bright near progress
+                0.8 + jitter * 0.2  // Bright near progress
             } else if intensity < 0.3 {
-                0.5 + rand::random::<f32>() * 0.3  // Medium brightness
-            } else {
-                0.2 + rand::random::<f32>() * 0.3  // Dimmer background
-            };
-            
-            // Convert HSV to RGB
-            let c = brightness * saturation;
-            let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
-            let m = brightness - c;
-            
-            let (r, g, b) = if hue < 60.0 {
-                (c, x, 0.0)
-            } else if hue < 120.0 {
-                (x, c, 0.0)
-            } else if hue < 180.0 {
-                (0.0, c, x)
-            } else if hue < 240.0 {
-                (0.0, x, c)
-            } else if hue < 300.0 {
-                (x, 0.0, c)
+                0.5 + jitter * 0.3  // Medium brightness
             } else {
-                (c, 0.0, x)
+                0.2 + jitter * 0.3  // Dimmer background
             };
-            
-            let color = Color::Rgb(
-                ((r + m) * 255.0) as u8,
-                ((g + m) * 255.0) as u8,
-                ((b + m) * 255.0) as u8
-            );
-            
-            let mut line_text = String::new();
-            for _ in 0..width {
-                if rand::random::<f32>() < glitch_intensity {
-                    line_text.push_str(glitch_chars[char_idx]);
-                } else {
-                    line_text.push(' ');
-                }
-            }
-            
-            bg_lines.push(Line::from(Span::styled(line_text, Style::default().fg(color))));
+
+            let color = hsv_to_rgb(hue, saturation, brightness);
+
+            bg_lines.push(Line::from(Span::styled(glitch.row(i), Style::default().fg(color))));
         }
-        
+
         let bg = Paragraph::new(bg_lines);
         frame.render_widget(bg, size);
-        
+
+        self.render_reveal_overlay(frame, progress, lang_name);
+    }
+
+    /// The reveal popup (slot machine, final language, translation spinner),
+    /// shared between the glitch and reduced-motion backgrounds.
+    fn render_reveal_overlay(&self, frame: &mut Frame, progress: f32, lang_name: &str) {
+        let size = frame.size();
+
         // Build the overlay content
         let mut message = vec![];
         
@@ -1927,31 +4790,9 @@ impl App {
             let hue = (self.glitch_frame as f32 * 17.0 + progress * 360.0) % 360.0;
             let saturation = 0.8 + rand::random::<f32>() * 0.2;
             let brightness = 0.7 + rand::random::<f32>() * 0.3;
-            
-            let c = brightness * saturation;
-            let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
-            let m = brightness - c;
-            
-            let (r, g, b) = if hue < 60.0 {
-                (c, x, 0.0)
-            } else if hue < 120.0 {
-                (x, c, 0.0)
-            } else if hue < 180.0 {
-                (0.0, c, x)
-            } else if hue < 240.0 {
-                (0.0, x, c)
-            } else if hue < 300.0 {
-                (x, 0.0, c)
-            } else {
-                (c, 0.0, x)
-            };
-            
-            let color = Color::Rgb(
-                ((r + m) * 255.0) as u8,
-                ((g + m) * 255.0) as u8,
-                ((b + m) * 255.0) as u8
-            );
-            
+
+            let color = hsv_to_rgb(hue, saturation, brightness);
+
             for line in ascii_art {
                 message.push(Line::from(Span::styled(
                     line,
@@ -2000,17 +4841,7 @@ impl App {
                 message.push(Line::from(""));
                 // Show loading animation if translation isn't ready yet
                 if !self.translation_ready() {
-                    // Spinner animation
-                    let spinner = match self.glitch_frame % 8 {
-                        0 => "⠋",
-                        1 => "⠙",
-                        2 => "⠹",
-                        3 => "⠸",
-                        4 => "⠼",
-                        5 => "⠴",
-                        6 => "⠦",
-                        _ => "⠧",
-                    };
+                    let spinner = self.spinner_glyph();
 
                     // Animated progress bar (bounces back and forth)
                     let bar_width = 20;
@@ -2084,90 +4915,83 @@ impl App {
         frame.render_widget(popup, popup_area);
     }
 
-    fn render_transition(&self, frame: &mut Frame, _progress: f32) {
+    fn render_transition(&mut self, frame: &mut Frame, _progress: f32) {
         let size = frame.size();
         let progress = if let AppState::Transitioning(p) = self.state {
             p
         } else {
             0.0
         };
-        
+
+        if self.reduced_motion {
+            // Calm fade-in instead of the glitch field
+            self.render_calm_fill(frame, progress);
+            self.render_transition_overlay(frame, progress);
+            return;
+        }
+
         // Create glitch effect background
         let glitch_chars = ["█", "▓", "▒", "░", "▄", "▀", "▌", "▐"];
-        let mut lines = Vec::new();
         let char_idx = (self.glitch_frame % glitch_chars.len()) as usize;
-        
+
         let height = size.height as usize;
         let width = size.width as usize;
-        
+
+        self.glitch.ensure_size(width, height);
+        self.glitch.advance();
+        let width_f = width.max(1) as f32;
+        self.glitch.fill_rows(glitch_chars[char_idx], |_, j| {
+            progress + (j as f32 / width_f * 0.3)
+        });
+
+        let glitch = &self.glitch;
+        let glitch_frame = self.glitch_frame;
+        let mut lines = Vec::with_capacity(height);
+
         for i in 0..height {
             let intensity = ((i as f32 / height as f32) - progress).abs();
             let wave = (i as f32 * 0.1 + progress * 10.0).sin();
-            let phase = (self.glitch_frame as f32 * 0.1 + i as f32 * 0.05).sin();
-            
+            let phase = (glitch_frame as f32 * 0.1 + i as f32 * 0.05).sin();
+
             // Generate random rainbow colors - full spectrum
-            let hue_base = (i as f32 * 7.0 + self.glitch_frame as f32 * 3.0) % 360.0;
+            let hue_base = (i as f32 * 7.0 + glitch_frame as f32 * 3.0) % 360.0;
             let hue_offset = wave * 60.0 + phase * 40.0;
             let hue = (hue_base + hue_offset).rem_euclid(360.0);
-            
+
             // Vary saturation and brightness based on intensity
+            let jitter = glitch.jitter(i);
             let saturation = if intensity < 0.1 {
-                0.9 + rand::random::<f32>() * 0.1  // Very saturated near progress
+                0.9 + jitter * 0.1  // Very saturated near progress
             } else if intensity < 0.3 {
-                0.6 + rand::random::<f32>() * 0.3  // Medium saturation
+                0.6 + jitter * 0.3  // Medium saturation
             } else {
-                0.3 + rand::random::<f32>() * 0.4  // Lower saturation
+                0.3 + jitter * 0.4  // Lower saturation
             };
-            
+
             let brightness = if intensity < 0.1 {
-                0.8 + rand::random::<f32>() * 0.2  // Bright near progress
+                0.8 + jitter * 0.2  // Bright near progress
             } else if intensity < 0.3 {
-                0.5 + rand::random::<f32>() * 0.3  // Medium brightness
-            } else {
-                0.2 + rand::random::<f32>() * 0.3  // Dimmer background
-            };
-            
-            // Convert HSV to RGB
-            let c = brightness * saturation;
-            let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
-            let m = brightness - c;
-            
-            let (r, g, b) = if hue < 60.0 {
-                (c, x, 0.0)
-            } else if hue < 120.0 {
-                (x, c, 0.0)
-            } else if hue < 180.0 {
-                (0.0, c, x)
-            } else if hue < 240.0 {
-                (0.0, x, c)
-            } else if hue < 300.0 {
-                (x, 0.0, c)
+                0.5 + jitter * 0.3  // Medium brightness
             } else {
-                (c, 0.0, x)
+                0.2 + jitter * 0.3  // Dimmer background
             };
-            
-            let color = Color::Rgb(
-                ((r + m) * 255.0) as u8,
-                ((g + m) * 255.0) as u8,
-                ((b + m) * 255.0) as u8
-            );
-            
-            let mut line_text = String::new();
-            for j in 0..width {
-                let density = progress + (j as f32 / width as f32 * 0.3);
-                if rand::random::<f32>() < density {
-                    line_text.push_str(glitch_chars[char_idx]);
-                } else {
-                    line_text.push(' ');
-                }
-            }
-            
-            lines.push(Line::from(Span::styled(line_text, Style::default().fg(color))));
+
+            let color = hsv_to_rgb(hue, saturation, brightness);
+
+            lines.push(Line::from(Span::styled(glitch.row(i), Style::default().fg(color))));
         }
-        
+
         let bg = Paragraph::new(lines);
         frame.render_widget(bg, size);
-        
+
+        self.render_transition_overlay(frame, progress);
+    }
+
+    /// The language-roulette popup, shared between the glitch and
+    /// reduced-motion backgrounds.
+    fn render_transition_overlay(&self, frame: &mut Frame, progress: f32) {
+        let size = frame.size();
+
         // Overlay language roulette on top of glitch effect
         let languages = Language::all();
         let spin_idx = ((progress * 30.0) as usize) % languages.len();
@@ -2197,31 +5021,9 @@ impl App {
         let hue = (self.glitch_frame as f32 * 17.0 + progress * 360.0) % 360.0;
         let saturation = 0.8 + rand::random::<f32>() * 0.2;
         let brightness = 0.7 + rand::random::<f32>() * 0.3;
-        
-        let c = brightness * saturation;
-        let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
-        let m = brightness - c;
-        
-        let (r, g, b) = if hue < 60.0 {
-            (c, x, 0.0)
-        } else if hue < 120.0 {
-            (x, c, 0.0)
-        } else if hue < 180.0 {
-            (0.0, c, x)
-        } else if hue < 240.0 {
-            (0.0, x, c)
-        } else if hue < 300.0 {
-            (x, 0.0, c)
-        } else {
-            (c, 0.0, x)
-        };
-        
-        let color = Color::Rgb(
-            ((r + m) * 255.0) as u8,
-            ((g + m) * 255.0) as u8,
-            ((b + m) * 255.0) as u8
-        );
-        
+
+        let color = hsv_to_rgb(hue, saturation, brightness);
+
         for line in ascii_art {
             message.push(Line::from(Span::styled(
                 line,
@@ -2264,19 +5066,21 @@ impl App {
         let size = frame.size();
         
         // Theme colors
-        let gold = Color::Rgb(255, 191, 0);
-        let bronze = Color::Rgb(139, 90, 43);
-        let purple = Color::Rgb(147, 112, 219);
-        
-        let score_percent = (results.passed as f32 / results.total as f32 * 100.0) as u8;
-        let (score_color, score_msg) = if score_percent == 100 {
-            (gold, "◈ FLAWLESS VICTORY ◈") // Gold
+        let gold = self.theme.gold;
+        let bronze = self.theme.bronze;
+        let purple = self.theme.purple;
+
+        let score_percent = results.score_percent();
+        let (score_color, score_msg) = if results.total == 0 {
+            (self.theme.warn, "◇ NO TEST CASES ◇")
+        } else if score_percent == 100 {
+            (gold, "◈ FLAWLESS VICTORY ◈")
         } else if score_percent >= 80 {
-            (Color::Rgb(100, 200, 130), "◇ WELL DONE ◇") // Soft green
+            (self.theme.success, "◇ WELL DONE ◇")
         } else if score_percent >= 50 {
-            (Color::Rgb(255, 200, 80), "◇ PROGRESS MADE ◇") // Warm yellow
+            (self.theme.warn, "◇ PROGRESS MADE ◇")
         } else {
-            (Color::Rgb(255, 100, 100), "◇ TOWER ENDURES ◇") // Soft red
+            (self.theme.error, "◇ TOWER ENDURES ◇")
         };
 
         // Create centered layout with border colors
@@ -2285,7 +5089,7 @@ impl App {
         } else if score_percent >= 80 {
             purple
         } else if score_percent >= 50 {
-            Color::Rgb(180, 140, 80)
+            self.theme.amber
         } else {
             bronze
         };
@@ -2383,17 +5187,62 @@ impl App {
         
         // Summary message with mystical flavor
         let summary = format!("⧗ Conquered {} of {} trials in the tower ⧗", results.passed, results.total);
-        main_text.push(Line::from(Span::styled(summary, Style::default().fg(Color::Rgb(200, 200, 200)))));
-        
+        main_text.push(Line::from(Span::styled(summary, Style::default().fg(self.theme.text))));
+        if self.hints_revealed > 0 {
+            main_text.push(Line::from(Span::styled(
+                format!("({} hint{} consulted)", self.hints_revealed, if self.hints_revealed == 1 { "" } else { "s" }),
+                Style::default().fg(self.theme.text_faint),
+            )));
+        }
+
+        // Letter grade folds pass rate, hints and solve time into one verdict
+        if results.total > 0 {
+            let grade = self.grade(results);
+            main_text.push(Line::from(""));
+            main_text.push(Line::from(Span::styled(
+                format!("⟡ RANK {} ⟡", grade.letter()),
+                Style::default()
+                    .fg(grade.color(&self.theme))
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
         main_text.push(Line::from(""));
         main_text.push(Line::from(Span::styled("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━", Style::default().fg(bronze))));
+        // Learning aid: after repeated failures, offer the reference solution
+        if self.solution_offer_active() {
+            main_text.push(Line::from(""));
+            main_text.push(Line::from(vec![
+                Span::styled("Stuck? Press ", Style::default().fg(self.theme.text_faint)),
+                Span::styled("L", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    " to study a reference solution",
+                    Style::default().fg(self.theme.text_faint),
+                ),
+            ]));
+        }
+
         main_text.push(Line::from(""));
         main_text.push(Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Color::Rgb(140, 140, 140))),
+            Span::styled("Press ", Style::default().fg(self.theme.text_faint)),
             Span::styled("R", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
-            Span::styled(" to continue  ┃  Press ", Style::default().fg(Color::Rgb(140, 140, 140))),
-            Span::styled("Q", Style::default().fg(Color::Rgb(180, 80, 80)).add_modifier(Modifier::BOLD)),
-            Span::styled(" to quit", Style::default().fg(Color::Rgb(140, 140, 140))),
+            Span::styled(" to retry  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("C", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" to edit with results docked  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("N", Style::default().fg(purple).add_modifier(Modifier::BOLD)),
+            Span::styled(" for a new problem  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("S", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" for stats  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("H", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" for history  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("E", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" to export  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("V", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" for verbose  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("F", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" for first fail  ┃  Press ", Style::default().fg(self.theme.text_faint)),
+            Span::styled("Q", Style::default().fg(self.theme.error).add_modifier(Modifier::BOLD)),
+            Span::styled(" to quit", Style::default().fg(self.theme.text_faint)),
         ]));
 
         let main_block = Block::default()
@@ -2413,17 +5262,31 @@ impl App {
         ];
 
         for result in &results.details {
-            let status_symbol = if result.passed { "◆" } else { "◇" };
-            let status_color = if result.passed { 
-                Color::Rgb(100, 200, 130) 
-            } else { 
-                Color::Rgb(255, 100, 100)
+            // With accessible labels on, outcomes don't rely on hue alone
+            let status_symbol = if self.accessible_labels {
+                if result.passed { "✓ PASS" } else { "✗ FAIL" }
+            } else if result.passed {
+                "◆"
+            } else {
+                "◇"
+            };
+            let status_color = if result.passed {
+                self.theme.success
+            } else {
+                self.theme.error
             };
             
+            // Ad-hoc cases sit after the built-ins and get labelled as such
+            let custom_tag = if result.case_number > self.problem.test_cases.len() {
+                " (yours)"
+            } else {
+                ""
+            };
             scoreboard_text.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(status_symbol, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
-                Span::styled(format!(" Trial #{}", result.case_number), Style::default().fg(Color::Rgb(200, 200, 200)).add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" Trial #{}", result.case_number), Style::default().fg(self.theme.text).add_modifier(Modifier::BOLD)),
+                Span::styled(custom_tag, Style::default().fg(self.theme.amber)),
             ]));
             
             // Compact display - use owned String
@@ -2434,23 +5297,23 @@ impl App {
             };
             
             scoreboard_text.push(Line::from(vec![
-                Span::styled("    Input: ", Style::default().fg(Color::Rgb(140, 140, 140))),
-                Span::styled(input_display, Style::default().fg(Color::Rgb(180, 180, 180))),
+                Span::styled("    Input: ", Style::default().fg(self.theme.text_faint)),
+                Span::styled(input_display, Style::default().fg(self.theme.text_dim)),
             ]));
             
             if result.passed {
                 scoreboard_text.push(Line::from(vec![
-                    Span::styled("    ✓ ", Style::default().fg(Color::Rgb(100, 200, 130))),
-                    Span::styled(result.expected.clone(), Style::default().fg(Color::Rgb(100, 200, 130))),
+                    Span::styled("    ✓ ", Style::default().fg(self.theme.success)),
+                    Span::styled(result.expected.clone(), Style::default().fg(self.theme.success)),
                 ]));
             } else {
                 scoreboard_text.push(Line::from(vec![
                     Span::styled("    Expected: ", Style::default().fg(purple)),
-                    Span::styled(result.expected.clone(), Style::default().fg(Color::Rgb(200, 200, 200))),
+                    Span::styled(result.expected.clone(), Style::default().fg(self.theme.text)),
                 ]));
                 scoreboard_text.push(Line::from(vec![
-                    Span::styled("    Got: ", Style::default().fg(Color::Rgb(255, 100, 100))),
-                    Span::styled(result.actual.clone(), Style::default().fg(Color::Rgb(200, 200, 200))),
+                    Span::styled("    Got: ", Style::default().fg(self.theme.error)),
+                    Span::styled(result.actual.clone(), Style::default().fg(self.theme.text)),
                 ]));
             }
             scoreboard_text.push(Line::from(""));
@@ -2462,11 +5325,17 @@ impl App {
             .border_style(Style::default().fg(bronze).add_modifier(Modifier::BOLD))
             .title(Span::styled(" ◇ TRIALS ◇ ", Style::default().fg(gold).add_modifier(Modifier::BOLD)));
 
+        // Clamp so the last page stays full (↑/↓ scroll, F jumps to the
+        // first failing trial)
+        let visible = main_layout[1].height.saturating_sub(2) as usize;
+        let max_scroll = scoreboard_text.len().saturating_sub(visible);
+        let scroll = self.results_scroll.min(max_scroll);
+
         let scoreboard_paragraph = Paragraph::new(scoreboard_text)
             .block(scoreboard_block)
             .alignment(Alignment::Left)
             .wrap(Wrap { trim: false })
-            .scroll((0, 0));
+            .scroll((scroll as u16, 0));
 
         frame.render_widget(main_paragraph, main_layout[0]);
         frame.render_widget(scoreboard_paragraph, main_layout[1]);
@@ -2596,3 +5465,220 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::problem::TestResult;
+
+    #[test]
+    fn starter_code_covers_every_problem_language_pair() {
+        for problem in Problem::all() {
+            for language in Language::all() {
+                let starter = get_starter_code(&problem, language);
+                assert!(
+                    starter.contains(problem.function_name_for(language).as_str()),
+                    "starter for problem {} in {} is missing the function name",
+                    problem.id,
+                    language.display_name()
+                );
+                assert!(
+                    !starter.contains("..."),
+                    "starter for problem {} in {} fell into the placeholder arm",
+                    problem.id,
+                    language.display_name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn output_lines_truncate_to_panel_width() {
+        assert_eq!(truncate_with_ellipsis("short", 10), "short");
+        assert_eq!(truncate_with_ellipsis("exactly ten", 11), "exactly ten");
+        assert_eq!(truncate_with_ellipsis("a very long error line", 10), "a very lo…");
+        // A zero-width panel (border-only rect) passes text through untouched
+        assert_eq!(truncate_with_ellipsis("anything", 0), "anything");
+    }
+
+    #[test]
+    fn output_cap_counts_dropped_lines_without_eating_survivors() {
+        let mut app = App::new();
+        app.max_output_lines = 3;
+        for i in 0..5 {
+            app.push_output_line(OutputLine {
+                text: format!("line {}", i),
+                is_error: false,
+            });
+        }
+
+        assert_eq!(app.output_lines_dropped, 2);
+        let texts: Vec<&str> = app.execution_output.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, ["line 2", "line 3", "line 4"]);
+    }
+
+    #[test]
+    fn footer_clicks_land_on_their_own_labels() {
+        // Both footer prefixes: timer while a swap is pending, and the
+        // fixed-language banner — the hit-boxes must track the spans either way
+        for prefix in [vec!["⧗ ", "42s"], vec!["◈ ", "Fixed language"]] {
+            let mut labels = prefix.clone();
+            labels.extend([
+                " ┃ ", "^S", " Submit ", "^R", " New ", "^C", " Run ", "^T", " Swap ", "^B",
+                " Focus ", "^Q", " Quit",
+            ]);
+            let spans: Vec<Span> = labels.iter().map(|l| Span::raw(*l)).collect();
+            let line: String = labels.concat();
+
+            // Width exactly the line length, so the centered line starts at x=0
+            // and a button's range indexes straight into the string
+            let area = Rect::new(0, 0, line.chars().count() as u16, 1);
+            let buttons = footer_hit_boxes(&spans, area);
+            let under = |from: u16, to: u16| -> String {
+                line.chars()
+                    .skip(from as usize)
+                    .take((to - from) as usize)
+                    .collect()
+            };
+
+            assert_eq!(buttons.len(), 4);
+            assert!(matches!(buttons[0].2, FooterAction::Submit));
+            assert_eq!(under(buttons[0].0, buttons[0].1), "^S Submit ");
+            assert!(matches!(buttons[1].2, FooterAction::NewProblem));
+            assert_eq!(under(buttons[1].0, buttons[1].1), "^R New ");
+            assert!(matches!(buttons[2].2, FooterAction::Run));
+            assert_eq!(under(buttons[2].0, buttons[2].1), "^C Run ");
+            assert!(matches!(buttons[3].2, FooterAction::Quit));
+            assert_eq!(under(buttons[3].0, buttons[3].1), "^Q Quit");
+        }
+    }
+
+    #[test]
+    fn base64_matches_rfc_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    /// A coding-state app on a manual clock, with the grace period already
+    /// behind it so the swap timer behaves as it does mid-session
+    fn app_on_manual_clock() -> (App, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::new();
+        app.clock = clock.clone();
+        // Re-anchor the timestamps `new()` took from the real clock
+        let now = clock.now();
+        app.problem_loaded_at = now;
+        app.last_tick_at = now;
+        app.last_session_save = now;
+        clock.advance(app.grace_period);
+        app.last_randomize = clock.now();
+        (app, clock)
+    }
+
+    #[tokio::test]
+    async fn countdown_starts_once_the_swap_threshold_passes() {
+        let (mut app, clock) = app_on_manual_clock();
+        assert_eq!(app.state, AppState::Coding);
+
+        // Just short of the threshold: still coding
+        let threshold = app.randomize_interval - Duration::from_secs(COUNTDOWN_SECS);
+        clock.advance(threshold - Duration::from_secs(1));
+        app.tick();
+        assert_eq!(app.state, AppState::Coding);
+
+        // Past it: the countdown begins with a language pre-selected
+        clock.advance(Duration::from_secs(1));
+        app.tick();
+        assert!(matches!(app.state, AppState::Countdown(_)));
+        assert!(app.pending_language.is_some());
+    }
+
+    #[tokio::test]
+    async fn countdown_reaching_zero_starts_the_transition() {
+        let (mut app, clock) = app_on_manual_clock();
+        app.state = AppState::Countdown(1);
+        app.pending_language = Some(app.current_language.random_except_with_rng(&mut app.rng));
+        clock.advance(app.randomize_interval);
+
+        app.tick();
+        assert!(matches!(app.state, AppState::Transitioning(_)));
+    }
+
+    #[tokio::test]
+    async fn transition_progress_completes_into_revealing() {
+        let (mut app, clock) = app_on_manual_clock();
+        app.state = AppState::Transitioning(0.99);
+        app.transition_start = Some(clock.now());
+        // 200ms caps the frame delta at the full MAX_FRAME_DELTA_SECS,
+        // enough to push 0.99 over 1.0
+        clock.advance(Duration::from_millis(200));
+
+        app.tick();
+        assert!(matches!(app.state, AppState::Revealing(_)));
+    }
+
+    #[tokio::test]
+    async fn reveal_holds_at_099_until_translation_arrives() {
+        let (mut app, clock) = app_on_manual_clock();
+        app.state = AppState::Revealing(0.99);
+        app.transition_start = Some(clock.now());
+        app.pending_language = Some(app.current_language.random_except_with_rng(&mut app.rng));
+        app.pending_translation = None; // Translation still in flight
+        clock.advance(Duration::from_millis(200));
+
+        app.tick();
+        assert!(matches!(app.state, AppState::Revealing(p) if (p - 0.99).abs() < f32::EPSILON));
+
+        // Once the translation lands, the reveal completes back into Coding
+        app.pending_translation = Some(TranslationEvent::Failure("timeout".to_string()));
+        clock.advance(Duration::from_millis(200));
+        app.tick();
+        assert_eq!(app.state, AppState::Coding);
+    }
+
+    #[tokio::test]
+    async fn submitting_caps_at_95_percent_without_results() {
+        let (mut app, _clock) = app_on_manual_clock();
+        app.state = AppState::Submitting(0.94, None);
+        // Output already arrived, so the compile-phase hold doesn't apply
+        app.execution_output.push(OutputLine {
+            text: "Sending code to Piston API...".to_string(),
+            is_error: false,
+        });
+
+        for _ in 0..20 {
+            app.tick();
+        }
+        match app.state {
+            AppState::Submitting(progress, None) => {
+                assert!(progress <= SUBMIT_WAIT_PHASE_END + f32::EPSILON)
+            }
+            ref other => panic!("expected Submitting without results, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn submitting_with_results_finishes_into_results() {
+        let (mut app, _clock) = app_on_manual_clock();
+        let results = TestResults {
+            total: 1,
+            passed: 1,
+            failed: 0,
+            details: vec![TestResult {
+                case_number: 1,
+                passed: true,
+                input: "2".to_string(),
+                expected: "1".to_string(),
+                actual: "1".to_string(),
+            }],
+        };
+        app.state = AppState::Submitting(0.99, Some(results));
+
+        app.tick();
+        assert!(matches!(app.state, AppState::Results(ref r) if r.passed == 1));
+    }
+}
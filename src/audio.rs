@@ -1,12 +1,41 @@
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
+use std::path::PathBuf;
+
+/// Locate the assets directory once at startup: `BABEL_ASSETS_DIR` wins,
+/// then `assets/` in the working directory, then next to the executable
+/// (installed binaries), then the parent directory. Probing per-sound made
+/// audio silently vanish when the binary was launched from elsewhere.
+fn resolve_assets_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("BABEL_ASSETS_DIR") {
+        let path = PathBuf::from(dir);
+        if path.is_dir() {
+            return Some(path);
+        }
+    }
+
+    let mut candidates = vec![PathBuf::from("assets")];
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            candidates.push(dir.join("assets"));
+        }
+    }
+    candidates.push(PathBuf::from("../assets"));
+
+    candidates.into_iter().find(|path| path.is_dir())
+}
 
 pub struct AudioPlayer {
     _stream: OutputStream,
     _stream_handle: OutputStreamHandle,
-    sink: Option<Sink>,
+    /// The looping background layer (countdown, submission). Replaced when a
+    /// new loop starts; stoppable without touching one-shots.
+    loop_sink: Option<Sink>,
+    /// Short fire-and-forget stings that layer over the loop. Finished sinks
+    /// are pruned lazily on the next play.
+    oneshot_sinks: Vec<Sink>,
+    assets_dir: Option<PathBuf>,
 }
 
 impl AudioPlayer {
@@ -15,7 +44,9 @@ impl AudioPlayer {
             Ok((stream, stream_handle)) => Some(Self {
                 _stream: stream,
                 _stream_handle: stream_handle,
-                sink: None,
+                loop_sink: None,
+                oneshot_sinks: Vec::new(),
+                assets_dir: resolve_assets_dir(),
             }),
             Err(_) => {
                 // Audio not available, continue silently
@@ -24,69 +55,114 @@ impl AudioPlayer {
         }
     }
 
-    fn play_file(&mut self, filename: &str, should_loop: bool, volume: f32) {
-        // Refresh audio stream
-        if let Ok((stream, stream_handle)) = OutputStream::try_default() {
-            self._stream = stream;
-            self._stream_handle = stream_handle;
+    /// Get a sink on the long-lived output stream. If the device went away
+    /// (headphones unplugged, default output changed), re-acquire the stream
+    /// once and retry rather than rebuilding it on every sound.
+    fn new_sink(&mut self) -> Option<Sink> {
+        if let Ok(sink) = Sink::try_new(&self._stream_handle) {
+            return Some(sink);
+        }
+        let (stream, stream_handle) = OutputStream::try_default().ok()?;
+        self._stream = stream;
+        self._stream_handle = stream_handle;
+        Sink::try_new(&self._stream_handle).ok()
+    }
+
+    /// Open and decode an asset. Prefers the resolved assets dir; a bare
+    /// filename still works for ad-hoc files dropped next to the binary.
+    fn open_source(&self, filename: &str) -> Option<Decoder<BufReader<File>>> {
+        let mut possible_paths = Vec::new();
+        if let Some(dir) = &self.assets_dir {
+            possible_paths.push(dir.join(filename));
         }
-        
-        // Try to find the audio file in common locations
-        let possible_paths = [
-            format!("assets/{}", filename),
-            filename.to_string(),
-            format!("../assets/{}", filename),
-        ];
+        possible_paths.push(PathBuf::from(filename));
 
         for path in &possible_paths {
-            if Path::new(path).exists() {
+            if path.exists() {
                 if let Ok(file) = File::open(path) {
-                    let reader = BufReader::new(file);
-                    if let Ok(source) = Decoder::new(reader) {
-                        if let Ok(sink) = Sink::try_new(&self._stream_handle) {
-                            sink.set_volume(volume); // Set the volume
-                            if should_loop {
-                                sink.append(source.repeat_infinite());
-                            } else {
-                                sink.append(source);
-                            }
-                            sink.play();
-                            self.sink = Some(sink);
-                            return;
-                        }
+                    if let Ok(source) = Decoder::new(BufReader::new(file)) {
+                        return Some(source);
                     }
                 }
             }
         }
+        None
+    }
+
+    /// Start (or replace) the looping background layer. One-shots keep playing.
+    fn play_loop(&mut self, filename: &str, volume: f32) {
+        self.stop_loop();
+        let Some(source) = self.open_source(filename) else {
+            return;
+        };
+        if let Some(sink) = self.new_sink() {
+            sink.set_volume(volume);
+            sink.append(source.repeat_infinite());
+            sink.play();
+            self.loop_sink = Some(sink);
+        }
+    }
+
+    /// Play a short sting layered over whatever loop is running.
+    fn play_oneshot(&mut self, filename: &str, volume: f32) {
+        self.oneshot_sinks.retain(|sink| !sink.empty());
+        let Some(source) = self.open_source(filename) else {
+            return;
+        };
+        if let Some(sink) = self.new_sink() {
+            sink.set_volume(volume);
+            sink.append(source);
+            sink.play();
+            self.oneshot_sinks.push(sink);
+        }
     }
 
     /// Play the start sound effect (when countdown begins)
     pub fn play_start_sfx(&mut self) {
-        self.stop(); // Stop any currently playing audio
-        self.play_file("start.mp3", true, 1.0); // Full volume
+        self.play_loop("start.mp3", 1.0); // Full volume
     }
 
     /// Play the end sound effect (when translation completes)
     pub fn play_end_sfx(&mut self) {
-        self.stop(); // Stop the start sound
-        self.play_file("end.mp3", false, 1.0); // Full volume
+        self.stop_loop(); // Stop the start loop
+        self.play_oneshot("end.mp3", 1.0); // Full volume
     }
 
     /// Play the countdown sound effect (during countdown window)
     pub fn play_countdown_sfx(&mut self) {
-        self.stop(); // Stop any currently playing audio
-        self.play_file("countdown.mp3", true, 0.3); // Reduced volume (40%)
+        self.play_loop("countdown.mp3", 0.3); // Reduced volume
     }
 
     /// Play the submission/results sound effect (when user submits with Ctrl+S)
     pub fn play_submission_sfx(&mut self) {
-        self.stop(); // Stop any currently playing audio
-        self.play_file("submission+results.mp3", true, 0.6);
+        self.play_loop("submission+results.mp3", 0.6);
+    }
+
+    /// Play the verdict sound when test results first land: triumphant on a
+    /// full pass, neutral on a partial, sad on a wipeout. Layers over the
+    /// submission loop; silent when the asset files aren't present.
+    pub fn play_results_sfx(&mut self, passed: usize, total: usize) {
+        let filename = if total > 0 && passed == total {
+            "results_win.mp3"
+        } else if passed > 0 {
+            "results_partial.mp3"
+        } else {
+            "results_lose.mp3"
+        };
+        self.play_oneshot(filename, 0.8);
+    }
+
+    /// Stop only the looping background layer
+    pub fn stop_loop(&mut self) {
+        if let Some(sink) = self.loop_sink.take() {
+            sink.stop();
+        }
     }
 
-    /// Stop the currently playing sound
-    pub fn stop(&mut self) {
-        if let Some(sink) = self.sink.take() {
+    /// Stop everything: the loop and any in-flight one-shots
+    pub fn stop_all(&mut self) {
+        self.stop_loop();
+        for sink in self.oneshot_sinks.drain(..) {
             sink.stop();
         }
     }
@@ -0,0 +1,43 @@
+use std::time::Instant;
+
+/// Time source for the app. Production code uses [`SystemClock`]; tests
+/// install [`ManualClock`] and advance it explicitly, which makes countdown
+/// and transition timing deterministic instead of racing the wall clock.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// The real wall clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[cfg(test)]
+pub struct ManualClock {
+    now: std::sync::Mutex<Instant>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock {
+            now: std::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn advance(&self, by: std::time::Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
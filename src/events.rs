@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+/// Structured events appended as JSONL to the file named by `BABEL_EVENT_LOG`,
+/// so external dashboards (e.g. a classroom projector) can `tail -f` a live
+/// session. When the env var is unset, `log_event` is a no-op.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    StateChanged {
+        state: String,
+    },
+    LanguageSwapped {
+        from: String,
+        to: String,
+    },
+    Submission {
+        problem: String,
+        language: String,
+        passed: usize,
+        total: usize,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    at: String,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+/// Append one event to the log. Failures are swallowed — a broken dashboard
+/// pipe should never take down the game.
+pub fn log_event(event: Event) {
+    use std::io::Write;
+
+    let path = match std::env::var("BABEL_EVENT_LOG") {
+        Ok(path) if !path.is_empty() => path,
+        _ => return,
+    };
+
+    let envelope = Envelope {
+        at: chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string(),
+        event: &event,
+    };
+    let Ok(line) = serde_json::to_string(&envelope) else {
+        return;
+    };
+
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+    {
+        let _ = writeln!(file, "{}", line);
+    }
+}
@@ -6,6 +6,21 @@ use std::env;
 use std::time::Duration;
 
 const DEFAULT_MODEL: &str = "gemini-3-flash-preview";
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+const SYSTEM_INSTRUCTION: &str = "You are a fast code translator. Think minimally. Output only code. Use correct syntax.";
+
+/// Which LLM provider to use for a translation request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationBackend {
+    Gemini,
+    OpenAi,
+}
+
+// LLM exchange diagnostics go through the shared leveled logger at debug
+// level (enable with BABEL_LOG=debug)
+fn debug_log(message: &str) {
+    crate::log::debug("LLM", message);
+}
 
 #[derive(Debug, Deserialize)]
 struct GenerateContentResponse {
@@ -27,22 +42,132 @@ struct Part {
     text: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Option<Vec<ChatChoice>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: Option<ChatMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    content: Option<String>,
+}
+
+/// Where the optional config file lives: `$XDG_CONFIG_HOME/babel/config.toml`
+/// or `~/.config/babel/config.toml`
+fn config_file_path() -> Option<std::path::PathBuf> {
+    let base = env::var("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| std::path::PathBuf::from(home).join(".config")))
+        .ok()?;
+    Some(base.join("babel").join("config.toml"))
+}
+
+/// Minimal TOML-subset lookup (`key = "value"` lines) so casual players can
+/// keep credentials in a config file instead of the environment
+fn config_file_value(key: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(config_file_path()?).ok()?;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some((k, v)) = line.split_once('=') {
+            if k.trim() == key {
+                let v = v.trim().trim_matches('"').trim_matches('\'');
+                if !v.is_empty() {
+                    return Some(v.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Resolve a credential: the env var (or `.env`) wins, then the config file
+fn credential(env_key: &str, file_key: &str) -> Option<String> {
+    env::var(env_key).ok().or_else(|| config_file_value(file_key))
+}
+
+/// Gemini credential from `GEMINI_API_KEY` or `api_key` in the config file
+pub fn gemini_key() -> Option<String> {
+    credential("GEMINI_API_KEY", "api_key")
+}
+
+/// OpenAI credential from `OPENAI_API_KEY` or `openai_api_key` in the config file
+pub fn openai_key() -> Option<String> {
+    credential("OPENAI_API_KEY", "openai_api_key")
+}
+
+fn missing_key_error(env_key: &str, file_key: &str) -> anyhow::Error {
+    let path = config_file_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "~/.config/babel/config.toml".to_string());
+    anyhow::anyhow!(
+        "{} is not set — export it (or put it in .env), or add `{} = \"...\"` to {}",
+        env_key,
+        file_key,
+        path
+    )
+}
+
+/// True when a translation error indicates API rate limiting or exhausted
+/// quota rather than a transient network failure. The app backs off from
+/// language swaps for a while instead of retrying into the same wall.
+pub fn is_rate_limited(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("rate-limited")
+        || lower.contains("quota")
+        || message.contains("429")
+        || message.contains("RESOURCE_EXHAUSTED")
+}
+
+/// Translate via the primary backend (Gemini), falling back to an
+/// OpenAI-compatible provider when Gemini fails or is not configured.
 pub async fn translate_code(prompt: &str) -> Result<String> {
-    let api_key = env::var("GEMINI_API_KEY")
-        .context("GEMINI_API_KEY is not set (check your .env or environment)")?;
-    let model = env::var("GEMINI_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+    let has_gemini = gemini_key().is_some();
+    let has_openai = openai_key().is_some();
 
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
-        model
-    );
+    if !has_gemini && has_openai {
+        return translate_via(TranslationBackend::OpenAi, prompt).await;
+    }
 
-    let payload = json!({
+    match translate_via(TranslationBackend::Gemini, prompt).await {
+        Ok(text) => Ok(text),
+        Err(gemini_err) => {
+            if has_openai {
+                debug_log(&format!(
+                    "Gemini failed ({}), falling back to OpenAI-compatible backend",
+                    gemini_err
+                ));
+                translate_via(TranslationBackend::OpenAi, prompt).await
+            } else {
+                Err(gemini_err)
+            }
+        }
+    }
+}
+
+/// Dispatch a translation request to a specific backend
+pub async fn translate_via(backend: TranslationBackend, prompt: &str) -> Result<String> {
+    match backend {
+        TranslationBackend::Gemini => translate_via_gemini(prompt).await,
+        TranslationBackend::OpenAi => translate_via_openai(prompt).await,
+    }
+}
+
+/// Build the Gemini request body. `generationConfig` (and its
+/// `thinkingConfig`, which only some models accept) can be omitted for
+/// models that reject it.
+fn gemini_payload(prompt: &str, with_generation_config: bool) -> serde_json::Value {
+    let mut payload = json!({
         "systemInstruction": {
             "parts": [
-                {
-                    "text": "You are a fast code translator. Think minimally. Output only code. Use correct syntax."
-                }
+                { "text": SYSTEM_INSTRUCTION }
             ]
         },
         "contents": [
@@ -51,8 +176,11 @@ pub async fn translate_code(prompt: &str) -> Result<String> {
                     { "text": prompt }
                 ]
             }
-        ],
-        "generationConfig": {
+        ]
+    });
+
+    if with_generation_config {
+        payload["generationConfig"] = json!({
             "temperature": 0.0,
             "maxOutputTokens": 4096,
             "topP": 0.95,
@@ -61,23 +189,72 @@ pub async fn translate_code(prompt: &str) -> Result<String> {
             "thinkingConfig": {
                 "thinkingLevel": "low"
             }
-        }
-    });
+        });
+    }
+
+    payload
+}
+
+async fn translate_via_gemini(prompt: &str) -> Result<String> {
+    let api_key = gemini_key().ok_or_else(|| missing_key_error("GEMINI_API_KEY", "api_key"))?;
+    let model = env::var("GEMINI_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
+        model
+    );
+
+    // GEMINI_NO_THINKING=1 skips generationConfig up front for models known
+    // to reject thinkingConfig
+    let with_config = !env::var("GEMINI_NO_THINKING").map(|v| v == "1").unwrap_or(false);
 
     let client = Client::builder()
         .timeout(Duration::from_secs(45))
         .build()
         .context("failed to build HTTP client")?;
 
-    let response = client
-        .post(url)
-        .header("x-goog-api-key", api_key)
-        .json(&payload)
+    let start = std::time::Instant::now();
+    let mut response = client
+        .post(&url)
+        .header("x-goog-api-key", &api_key)
+        .json(&gemini_payload(prompt, with_config))
         .send()
         .await
-        .context("failed to send Gemini request")?
+        .context("failed to send Gemini request")?;
+
+    // A 400 complaining about an unknown field usually means the model
+    // doesn't take thinkingConfig; retry once without generationConfig
+    if with_config && response.status() == reqwest::StatusCode::BAD_REQUEST {
+        let body = response.text().await.unwrap_or_default();
+        if body.contains("Unknown name") || body.contains("thinking") {
+            debug_log(&format!(
+                "Gemini 400 on generationConfig ({}), retrying without it",
+                model
+            ));
+            response = client
+                .post(&url)
+                .header("x-goog-api-key", &api_key)
+                .json(&gemini_payload(prompt, false))
+                .send()
+                .await
+                .context("failed to send Gemini request")?;
+        } else {
+            anyhow::bail!("Gemini request returned an error status: 400 Bad Request");
+        }
+    }
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        anyhow::bail!("Gemini translation rate-limited (HTTP 429)");
+    }
+
+    let response = response
         .error_for_status()
         .context("Gemini request returned an error status")?;
+    debug_log(&format!(
+        "Gemini request ({}) took {}ms",
+        model,
+        start.elapsed().as_millis()
+    ));
 
     let body: GenerateContentResponse = response
         .json()
@@ -99,6 +276,75 @@ pub async fn translate_code(prompt: &str) -> Result<String> {
         anyhow::bail!("Gemini response was empty");
     }
 
+    Ok(clean_translation(&text))
+}
+
+async fn translate_via_openai(prompt: &str) -> Result<String> {
+    let api_key =
+        openai_key().ok_or_else(|| missing_key_error("OPENAI_API_KEY", "openai_api_key"))?;
+    let model = env::var("OPENAI_MODEL").unwrap_or_else(|_| DEFAULT_OPENAI_MODEL.to_string());
+    let base_url = env::var("OPENAI_BASE_URL")
+        .unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
+
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+    let payload = json!({
+        "model": model,
+        "messages": [
+            { "role": "system", "content": SYSTEM_INSTRUCTION },
+            { "role": "user", "content": prompt }
+        ],
+        "temperature": 0.0,
+        "max_tokens": 4096
+    });
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(45))
+        .build()
+        .context("failed to build HTTP client")?;
+
+    let start = std::time::Instant::now();
+    let response = client
+        .post(url)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&payload)
+        .send()
+        .await
+        .context("failed to send OpenAI request")?;
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        anyhow::bail!("OpenAI translation rate-limited (HTTP 429)");
+    }
+    let response = response
+        .error_for_status()
+        .context("OpenAI request returned an error status")?;
+    debug_log(&format!(
+        "OpenAI request ({}) took {}ms",
+        model,
+        start.elapsed().as_millis()
+    ));
+
+    let body: ChatCompletionResponse = response
+        .json()
+        .await
+        .context("failed to parse OpenAI response")?;
+
+    let text = body
+        .choices
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|choice| choice.message)
+        .filter_map(|message| message.content)
+        .collect::<Vec<_>>()
+        .join("");
+
+    if text.trim().is_empty() {
+        anyhow::bail!("OpenAI response was empty");
+    }
+
+    Ok(clean_translation(&text))
+}
+
+pub(crate) fn clean_translation(text: &str) -> String {
     // Clean up any invalid mathematical notation that might have slipped through
     let cleaned = text
         .replace('→', "->")  // Mathematical arrow to ASCII arrow
@@ -123,5 +369,5 @@ pub async fn translate_code(prompt: &str) -> Result<String> {
         .replace("```\n", "")
         .replace("\n```", "");
 
-    Ok(cleaned.trim().to_string())
+    cleaned.trim().to_string()
 }
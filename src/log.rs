@@ -0,0 +1,84 @@
+//! Leveled file logging shared across the app.
+//!
+//! All diagnostics go to a single file (`BABEL_LOG_FILE`, default
+//! `code_arcade.log` in the working directory). `BABEL_LOG` sets the
+//! minimum level to record: `error`, `warn`, `info` or `debug`
+//! (default `warn`). The gameplay event log (`BABEL_EVENT_LOG`) is
+//! separate and unaffected.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Log severity, most to least severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+
+    /// Minimum level to record, from `BABEL_LOG`
+    fn threshold() -> Level {
+        match std::env::var("BABEL_LOG")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "debug" => Level::Debug,
+            "info" => Level::Info,
+            "error" => Level::Error,
+            _ => Level::Warn,
+        }
+    }
+}
+
+fn log_path() -> PathBuf {
+    std::env::var("BABEL_LOG_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("code_arcade.log"))
+}
+
+/// Append one entry. Logging failures are swallowed — diagnostics must
+/// never take the app down.
+pub fn write(level: Level, context: &str, message: &str) {
+    if level > Level::threshold() {
+        return;
+    }
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    let entry = format!("[{}] {:<5} {}: {}\n", timestamp, level.label(), context, message);
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path())
+    {
+        let _ = file.write_all(entry.as_bytes());
+    }
+}
+
+pub fn error(context: &str, message: &str) {
+    write(Level::Error, context, message);
+}
+
+pub fn warn(context: &str, message: &str) {
+    write(Level::Warn, context, message);
+}
+
+#[allow(dead_code)]
+pub fn info(context: &str, message: &str) {
+    write(Level::Info, context, message);
+}
+
+pub fn debug(context: &str, message: &str) {
+    write(Level::Debug, context, message);
+}
@@ -1,15 +1,19 @@
 mod app;
 mod audio;
+mod clock;
+mod events;
 mod languages;
 mod llm;
+mod log;
 mod problem;
 mod syntax;
+mod theme;
 
 use anyhow::Result;
 use app::{App, AppState};
 use audio::AudioPlayer;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, EnableMouseCapture, DisableMouseCapture},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, EnableMouseCapture, DisableMouseCapture, EnableBracketedPaste, DisableBracketedPaste},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -21,22 +25,42 @@ use std::time::Duration;
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
+    // Non-TUI subcommands are handled before touching the terminal
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(|a| a.as_str()) == Some("translate") {
+        return run_translate_once(&args[1..]).await;
+    }
+    if args.first().map(|a| a.as_str()) == Some("bench") {
+        return run_bench(&args[1..]).await;
+    }
+    if args.first().map(|a| a.as_str()) == Some("harness") {
+        return run_harness_preview(&args[1..]);
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Show a first frame immediately. Problem selection is instant today,
+    // but if loading ever grows to read files or fetch remote problem sets,
+    // launch shouldn't sit on a blank screen until the first real render.
+    terminal.draw(render_loading_frame)?;
+
     // Create app
     let mut app = App::new();
 
     // Main loop
     let result = run_app(&mut terminal, &mut app).await;
 
+    // Persist the resumable session on any exit path (BABEL_RESUME=1)
+    app.save_session();
+
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     terminal.show_cursor()?;
 
     if let Err(err) = result {
@@ -46,12 +70,275 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Minimal splash drawn before `App::new` runs, so startup never looks hung
+fn render_loading_frame(frame: &mut ratatui::Frame) {
+    use ratatui::layout::{Alignment, Rect};
+    use ratatui::widgets::Paragraph;
+
+    let size = frame.size();
+    let area = Rect {
+        x: 0,
+        y: size.height / 2,
+        width: size.width,
+        height: 2.min(size.height),
+    };
+    let splash = Paragraph::new("◆ TERMINAL OF BABEL ◆\nLoading...").alignment(Alignment::Center);
+    frame.render_widget(splash, area);
+}
+
+/// `babel translate --from python --to rust --file solution.py`
+/// Reads the file, translates it once via the LLM, and prints the result.
+async fn run_translate_once(args: &[String]) -> Result<()> {
+    let mut from = None;
+    let mut to = None;
+    let mut file = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--from" => from = iter.next().cloned(),
+            "--to" => to = iter.next().cloned(),
+            "--file" => file = iter.next().cloned(),
+            other => anyhow::bail!("unknown argument: {}", other),
+        }
+    }
+
+    let from = from.ok_or_else(|| anyhow::anyhow!("missing --from <language>"))?;
+    let to = to.ok_or_else(|| anyhow::anyhow!("missing --to <language>"))?;
+    let file = file.ok_or_else(|| anyhow::anyhow!("missing --file <path>"))?;
+
+    let from = languages::Language::from_name(&from)
+        .ok_or_else(|| anyhow::anyhow!("unknown source language: {}", from))?;
+    let to = languages::Language::from_name(&to)
+        .ok_or_else(|| anyhow::anyhow!("unknown target language: {}", to))?;
+
+    let code = std::fs::read_to_string(&file)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", file, e))?;
+
+    let prompt = languages::build_translation_prompt_with_signature(&code, from, to, None);
+    let translated = llm::translate_code(&prompt).await?;
+    println!("{}", translated);
+
+    Ok(())
+}
+
+/// `babel harness --problem 1 --lang python --code solution.py`
+/// Prints the exact Python harness that would be sent to Piston, without any
+/// network call — a dry run for checking new-problem harnesses. Non-Python
+/// submissions are LLM-converted to Python before execution, so for other
+/// languages the preview embeds the file unconverted.
+fn run_harness_preview(args: &[String]) -> Result<()> {
+    let mut problem_id = None;
+    let mut lang = None;
+    let mut code = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--problem" => problem_id = iter.next().cloned(),
+            "--lang" => lang = iter.next().cloned(),
+            "--code" => code = iter.next().cloned(),
+            other => anyhow::bail!("unknown argument: {}", other),
+        }
+    }
+
+    let problem_id = problem_id.ok_or_else(|| anyhow::anyhow!("missing --problem <id>"))?;
+    let lang = lang.ok_or_else(|| anyhow::anyhow!("missing --lang <language>"))?;
+    let code = code.ok_or_else(|| anyhow::anyhow!("missing --code <path>"))?;
+
+    let problem_id: usize = problem_id
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid problem id: {}", problem_id))?;
+    let problem = problem::Problem::all()
+        .into_iter()
+        .find(|p| p.id == problem_id)
+        .ok_or_else(|| anyhow::anyhow!("no problem with id {}", problem_id))?;
+    let lang = languages::Language::from_name(&lang)
+        .ok_or_else(|| anyhow::anyhow!("unknown language: {}", lang))?;
+
+    let code = std::fs::read_to_string(&code)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", code, e))?;
+
+    if lang != languages::Language::Python {
+        eprintln!(
+            "note: {} submissions are converted to Python before execution; \
+             the harness below embeds the file as-is",
+            lang.display_name()
+        );
+    }
+    println!("{}", problem::preview_python_harness(&problem, &code));
+
+    Ok(())
+}
+
+/// min/median/p95/max over a set of timings, in milliseconds. All zeros
+/// when there are no samples (e.g. every run in a batch failed).
+fn latency_summary(samples: &mut [u128]) -> (u128, u128, u128, u128) {
+    if samples.is_empty() {
+        return (0, 0, 0, 0);
+    }
+    samples.sort_unstable();
+    let min = samples[0];
+    let max = samples[samples.len() - 1];
+    let median = samples[samples.len() / 2];
+    let p95_idx = ((samples.len() as f64 * 0.95).ceil() as usize)
+        .saturating_sub(1)
+        .min(samples.len() - 1);
+    (min, median, samples[p95_idx], max)
+}
+
+/// `babel bench --langs python,rust --runs 3 [--translate] [--execute] [--json]`
+///
+/// Times LLM translations for every ordered language pair and/or Piston
+/// executions per language, and prints min/median/p95/max latencies. Useful
+/// for picking timeout values and spotting consistently slow languages.
+async fn run_bench(args: &[String]) -> Result<()> {
+    let mut langs = vec![languages::Language::Python, languages::Language::Rust];
+    let mut runs: usize = 3;
+    let mut do_translate = false;
+    let mut do_execute = false;
+    let mut as_json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--langs" => {
+                let list = iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("missing value for --langs"))?;
+                langs = list
+                    .split(',')
+                    .map(|name| {
+                        languages::Language::from_name(name.trim())
+                            .ok_or_else(|| anyhow::anyhow!("unknown language: {}", name))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+            }
+            "--runs" => {
+                runs = iter
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| anyhow::anyhow!("--runs takes a positive integer"))?;
+            }
+            "--translate" => do_translate = true,
+            "--execute" => do_execute = true,
+            "--json" => as_json = true,
+            other => anyhow::bail!("unknown argument: {}", other),
+        }
+    }
+    // Neither phase flag means both
+    if !do_translate && !do_execute {
+        do_translate = true;
+        do_execute = true;
+    }
+
+    let problem = problem::Problem::two_sum();
+    let mut rows: Vec<serde_json::Value> = Vec::new();
+
+    if do_translate {
+        if llm::gemini_key().is_none() && llm::openai_key().is_none() {
+            eprintln!("bench: no API key configured, skipping translation phase");
+        } else {
+            for &from in &langs {
+                for &to in &langs {
+                    if from == to {
+                        continue;
+                    }
+                    let code = app::get_starter_code(&problem, from);
+                    let prompt = languages::build_translation_prompt_with_signature(
+                        &code, from, to, None,
+                    );
+                    let mut samples = Vec::with_capacity(runs);
+                    for _ in 0..runs {
+                        let start = std::time::Instant::now();
+                        if let Err(e) = llm::translate_code(&prompt).await {
+                            eprintln!(
+                                "bench: translate {} -> {} failed: {}",
+                                from.display_name(),
+                                to.display_name(),
+                                e
+                            );
+                            continue;
+                        }
+                        samples.push(start.elapsed().as_millis());
+                    }
+                    if !samples.is_empty() {
+                        rows.push(bench_row("translate", from, Some(to), &mut samples));
+                    }
+                }
+            }
+        }
+    }
+
+    if do_execute {
+        for &lang in &langs {
+            let code = app::get_starter_code(&problem, lang);
+            let mut samples = Vec::with_capacity(runs);
+            for _ in 0..runs {
+                // The executor streams progress over this channel; bench only
+                // cares about wall time, so the events are discarded
+                let (tx, _rx) = tokio::sync::mpsc::channel(100);
+                let start = std::time::Instant::now();
+                problem::run_tests_on_piston(code.clone(), problem.clone(), lang, tx).await;
+                samples.push(start.elapsed().as_millis());
+            }
+            rows.push(bench_row("execute", lang, None, &mut samples));
+        }
+    }
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        for row in &rows {
+            println!(
+                "{:<9} {:<22} runs={:<3} min={}ms median={}ms p95={}ms max={}ms",
+                row["phase"].as_str().unwrap_or(""),
+                row["pair"].as_str().unwrap_or(""),
+                row["runs"],
+                row["min_ms"],
+                row["median_ms"],
+                row["p95_ms"],
+                row["max_ms"],
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn bench_row(
+    phase: &str,
+    from: languages::Language,
+    to: Option<languages::Language>,
+    samples: &mut [u128],
+) -> serde_json::Value {
+    let (min, median, p95, max) = latency_summary(samples);
+    let pair = match to {
+        Some(to) => format!("{} -> {}", from.display_name(), to.display_name()),
+        None => from.display_name().to_string(),
+    };
+    serde_json::json!({
+        "phase": phase,
+        "pair": pair,
+        "runs": samples.len(),
+        "min_ms": min,
+        "median_ms": median,
+        "p95_ms": p95,
+        "max_ms": max,
+    })
+}
+
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> Result<()> {
-    // 60 FPS tick rate
-    let tick_rate = Duration::from_millis(16);
+    // 60 FPS tick rate (30 FPS is plenty when animations are toned down)
+    let tick_rate = if app.reduced_motion {
+        Duration::from_millis(33)
+    } else {
+        Duration::from_millis(16)
+    };
     let mut last_tick = std::time::Instant::now();
     
     // Audio player for SFX
@@ -59,6 +346,7 @@ async fn run_app<B: ratatui::backend::Backend>(
     let mut audio_playing = false;
     let mut prev_state_is_countdown = false;
     let mut prev_state_is_submitting = false;
+    let mut prev_state_is_results = false;
 
     loop {
         // Render
@@ -105,6 +393,16 @@ async fn run_app<B: ratatui::backend::Backend>(
             } else if !is_submitting {
                 prev_state_is_submitting = false;
             }
+
+            // Verdict sting the moment results land (win/partial/lose)
+            if let AppState::Results(ref results) = app.state {
+                if !prev_state_is_results {
+                    player.play_results_sfx(results.passed, results.total);
+                    prev_state_is_results = true;
+                }
+            } else {
+                prev_state_is_results = false;
+            }
         }
 
         // Calculate timeout for next tick
@@ -128,14 +426,18 @@ async fn run_app<B: ratatui::backend::Backend>(
                             if key.code == KeyCode::Esc || key.code == KeyCode::Char('q') {
                                 // Stop audio before quitting
                                 if let Some(ref mut player) = audio_player {
-                                    player.stop();
+                                    player.stop_all();
                                 }
                                 return Ok(());
                             }
-                            // Stop audio on restart (R key)
-                            if key.code == KeyCode::Enter || key.code == KeyCode::Char('r') {
+                            // Stop audio on restart (R key) or new problem (N key)
+                            if key.code == KeyCode::Enter
+                                || key.code == KeyCode::Char('r')
+                                || key.code == KeyCode::Char('n')
+                                || key.code == KeyCode::Char('N')
+                            {
                                 if let Some(ref mut player) = audio_player {
-                                    player.stop();
+                                    player.stop_all();
                                 }
                                 prev_state_is_submitting = false; // Reset state tracker
                             }
@@ -147,10 +449,18 @@ async fn run_app<B: ratatui::backend::Backend>(
                 Event::Mouse(mouse) => {
                     app.handle_mouse(mouse);
                 }
+                Event::Paste(text) => {
+                    app.handle_paste(&text);
+                }
                 _ => {}
             }
         }
 
+        // Footer "Quit" clicks exit through a flag rather than the key path
+        if app.should_quit {
+            return Ok(());
+        }
+
         // Tick
         if last_tick.elapsed() >= tick_rate {
             app.tick();
@@ -1,55 +1,37 @@
+use crate::log;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
-// Error logging helper
-fn log_error(context: &str, error: &str) {
-    use std::io::Write;
-    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-    let log_entry = format!("[{}] {}: {}\n", timestamp, context, error);
-    
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("code_arcade_errors.log")
-    {
-        let _ = file.write_all(log_entry.as_bytes());
-    }
-}
-
-// Piston-specific error logging with full details
-fn log_piston_error(language: &str, error_type: &str, details: &str) {
-    use std::io::Write;
-    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-    let log_entry = format!(
-        "[{}] Piston Error - Language: {}, Type: {}\nDetails: {}\n---\n",
-        timestamp, language, error_type, details
-    );
-    
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("piston_errors.log")
-    {
-        let _ = file.write_all(log_entry.as_bytes());
-    }
-}
-
-// Log full Piston request/response for debugging
-fn log_piston_full_exchange(language: &str, request_code: &str, response: &str) {
-    use std::io::Write;
-    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-    let log_entry = format!(
-        "[{}] === Piston Full Exchange: {} ===\n\n--- Generated Code ---\n{}\n\n--- Response ---\n{}\n\n=== End Exchange ===\n\n",
-        timestamp, language, request_code, response
+// Log full Piston request/response for debugging (debug level — enable
+// with BABEL_LOG=debug)
+fn log_piston_full_exchange(language: &str, request_code: &str, response: &str, elapsed: Option<std::time::Duration>) {
+    let timing = elapsed
+        .map(|e| format!(" (took {}ms)", e.as_millis()))
+        .unwrap_or_default();
+    log::debug(
+        "Piston exchange",
+        &format!(
+            "{}{}\n--- Generated Code ---\n{}\n--- Response ---\n{}\n=== End Exchange ===",
+            language, timing, request_code, response
+        ),
     );
-    
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("piston_full.log")
-    {
-        let _ = file.write_all(log_entry.as_bytes());
+}
+
+/// Convert a snake_case identifier to camelCase
+fn to_camel_case(snake: &str) -> String {
+    let mut result = String::new();
+    let mut upper_next = false;
+    for ch in snake.chars() {
+        if ch == '_' {
+            upper_next = true;
+        } else if upper_next {
+            result.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            result.push(ch);
+        }
     }
+    result
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,8 +57,22 @@ pub struct Problem {
     pub function_name: String,
     pub parameters: Vec<Parameter>,
     pub return_type: String,
+    /// Epsilon for float comparisons when `return_type` is `float`/`float[]`;
+    /// `None` uses [`DEFAULT_FLOAT_TOLERANCE`]. Ignored for other types.
+    #[serde(default)]
+    pub float_tolerance: Option<f64>,
+    /// Canonical Python solution, offered as a learning aid after repeated
+    /// failed submissions (see `App::solution_offer_active`)
+    #[serde(default)]
+    pub reference_solution: Option<String>,
+    /// Progressive hints, revealed one at a time with Ctrl+H while coding
+    #[serde(default)]
+    pub hints: Vec<String>,
 }
 
+/// Tolerance used for `float`/`float[]` results unless a problem overrides it
+pub const DEFAULT_FLOAT_TOLERANCE: f64 = 1e-6;
+
 impl Problem {
     pub fn all() -> Vec<Problem> {
         vec![
@@ -86,20 +82,59 @@ impl Problem {
             Problem::palindrome_check(),
             Problem::fibonacci(),
         ]
+        .into_iter()
+        .filter(|problem| match problem.validate() {
+            Ok(()) => true,
+            Err(err) => {
+                log::error("Problem validation", &err);
+                false
+            }
+        })
+        .collect()
+    }
+
+    /// Sanity-check a problem definition. Scoring relies on a non-empty set
+    /// of test cases, so an empty one is rejected before it can surface as a
+    /// confusing 0/0 result.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.test_cases.is_empty() {
+            return Err(format!("problem \"{}\" has no test cases", self.title));
+        }
+        Ok(())
     }
 
-    pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
-        Problem::all().choose(&mut rng).unwrap().clone()
+    /// Draws from a caller-provided rng so seeded sessions (`BABEL_SEED`)
+    /// get a reproducible problem sequence
+    pub fn random_with_rng<R: rand::Rng>(rng: &mut R) -> Self {
+        Problem::all().choose(rng).unwrap().clone()
     }
 
-    pub fn random_except(&self) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn random_except_with_rng<R: rand::Rng>(&self, rng: &mut R) -> Self {
         let others: Vec<_> = Problem::all()
             .into_iter()
             .filter(|p| p.id != self.id)
             .collect();
-        others.choose(&mut rng).unwrap().clone()
+        others.choose(rng).unwrap().clone()
+    }
+
+    /// Idiomatic function name for a target language: camelCase where the
+    /// language convention expects it, snake_case otherwise. Used by starter
+    /// code so it matches what the harness looks up.
+    pub fn function_name_for(&self, language: Language) -> String {
+        match language {
+            Language::JavaScript
+            | Language::TypeScript
+            | Language::Java
+            | Language::Kotlin
+            | Language::Swift
+            | Language::Go
+            | Language::Haskell => to_camel_case(&self.function_name),
+            Language::Python
+            | Language::Rust
+            | Language::Lua
+            | Language::OCaml
+            | Language::Elixir => self.function_name.clone(),
+        }
     }
 
     /// Returns a type signature hint for the LLM, e.g.:
@@ -145,6 +180,21 @@ Output: [0,1]"#.to_string(),
                 "-10^9 <= target <= 10^9".to_string(),
                 "Only one valid answer exists.".to_string(),
             ],
+            float_tolerance: None,
+            reference_solution: Some(
+                r#"def two_sum(nums, target):
+    seen = {}
+    for i, n in enumerate(nums):
+        if target - n in seen:
+            return [seen[target - n], i]
+        seen[n] = i
+    return []"#
+                .to_string(),
+            ),
+            hints: vec![
+                "A nested loop works, but think about what you'd need to look up for each element.".to_string(),
+                "Store each value's index in a hash map; for each element check if (target - value) was already seen.".to_string(),
+            ],
             test_cases: vec![
                 TestCase {
                     input: vec!["[2,7,11,15]".to_string(), "9".to_string()],
@@ -192,6 +242,20 @@ Output: ["h","a","n","n","a","H"]"#.to_string(),
                 "1 <= s.length <= 10^5".to_string(),
                 "s[i] is a printable ascii character.".to_string(),
             ],
+            float_tolerance: None,
+            reference_solution: Some(
+                r#"def reverse_string(s):
+    left, right = 0, len(s) - 1
+    while left < right:
+        s[left], s[right] = s[right], s[left]
+        left += 1
+        right -= 1"#
+                .to_string(),
+            ),
+            hints: vec![
+                "The reversal must happen in-place; building a new array doesn't count.".to_string(),
+                "Walk two pointers from the ends toward the middle, swapping as they go.".to_string(),
+            ],
             test_cases: vec![
                 TestCase {
                     input: vec![r#"["h","e","l","l","o"]"#.to_string()],
@@ -234,6 +298,26 @@ Output: ["1","2","Fizz","4","Buzz","Fizz","7","8","Fizz","Buzz","11","Fizz","13"
             constraints: vec![
                 "1 <= n <= 10^4".to_string(),
             ],
+            float_tolerance: None,
+            reference_solution: Some(
+                r#"def fizz_buzz(n):
+    result = []
+    for i in range(1, n + 1):
+        if i % 15 == 0:
+            result.append("FizzBuzz")
+        elif i % 3 == 0:
+            result.append("Fizz")
+        elif i % 5 == 0:
+            result.append("Buzz")
+        else:
+            result.append(str(i))
+    return result"#
+                .to_string(),
+            ),
+            hints: vec![
+                "Check divisibility by 15 before 3 or 5, or the FizzBuzz case never fires.".to_string(),
+                "Numbers that match neither rule go in as strings, not integers.".to_string(),
+            ],
             test_cases: vec![
                 TestCase {
                     input: vec!["3".to_string()],
@@ -281,6 +365,17 @@ Explanation: After removing non-alphanumeric chars, s is ""."#.to_string(),
                 "1 <= s.length <= 2 * 10^5".to_string(),
                 "s consists only of printable ASCII characters.".to_string(),
             ],
+            float_tolerance: None,
+            reference_solution: Some(
+                r#"def is_palindrome(s):
+    cleaned = [c.lower() for c in s if c.isalnum()]
+    return cleaned == cleaned[::-1]"#
+                .to_string(),
+            ),
+            hints: vec![
+                "Strip everything that isn't alphanumeric and lowercase the rest before comparing.".to_string(),
+                "A string is a palindrome when it equals its own reverse.".to_string(),
+            ],
             test_cases: vec![
                 TestCase {
                     input: vec![r#""A man, a plan, a canal: Panama""#.to_string()],
@@ -331,6 +426,19 @@ Explanation: F(4) = F(3) + F(2) = 2 + 1 = 3."#.to_string(),
             constraints: vec![
                 "0 <= n <= 30".to_string(),
             ],
+            float_tolerance: None,
+            reference_solution: Some(
+                r#"def fibonacci(n):
+    a, b = 0, 1
+    for _ in range(n):
+        a, b = b, a + b
+    return a"#
+                .to_string(),
+            ),
+            hints: vec![
+                "Naive recursion recomputes the same values exponentially many times.".to_string(),
+                "Iterate with two running values (previous and current) and roll them forward n times.".to_string(),
+            ],
             test_cases: vec![
                 TestCase {
                     input: vec!["2".to_string()],
@@ -357,14 +465,14 @@ use tokio::sync::mpsc;
 use crate::app::{ExecutionEvent, OutputLine};
 use crate::languages::Language;
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct PistonRequest {
     language: String,
     version: String,
     files: Vec<PistonFile>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct PistonFile {
     name: String,
     content: String,
@@ -373,6 +481,10 @@ struct PistonFile {
 #[derive(Deserialize)]
 struct PistonResponse {
     run: PistonRunResult,
+    /// Present for compiled runtimes; a non-zero exit here means the run
+    /// stage output is meaningless
+    #[serde(default)]
+    compile: Option<PistonStage>,
 }
 
 #[derive(Deserialize)]
@@ -382,14 +494,298 @@ struct PistonRunResult {
     code: Option<i32>,
 }
 
+/// Piston's compile stage (same shape as the run stage)
+#[derive(Deserialize)]
+struct PistonStage {
+    stdout: String,
+    stderr: String,
+    code: Option<i32>,
+}
+
+/// Abstraction over the Piston execute call so the scoring pipeline
+/// can be tested without hitting the network
+trait PistonExecutor {
+    async fn execute(&self, request: &PistonRequest) -> Result<PistonResponse, String>;
+}
+
+/// Latest version per Piston language name, resolved from the `/runtimes`
+/// endpoint once per session. An empty map (fetch failed) makes every lookup
+/// fall back to the hardcoded version in the request.
+static PISTON_RUNTIMES: tokio::sync::OnceCell<std::collections::HashMap<String, String>> =
+    tokio::sync::OnceCell::const_new();
+
+/// `true` if version string `a` (dotted numerics, e.g. "3.12.0") is newer
+/// than `b`. Non-numeric segments compare as 0.
+fn version_newer(a: &str, b: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split('.').map(|seg| seg.parse().unwrap_or(0)).collect()
+    };
+    parse(a) > parse(b)
+}
+
+async fn fetch_piston_runtimes() -> std::collections::HashMap<String, String> {
+    #[derive(Deserialize)]
+    struct Runtime {
+        language: String,
+        version: String,
+    }
+
+    let client = reqwest::Client::new();
+    let res = client
+        .get("https://emkc.org/api/v2/piston/runtimes")
+        .send()
+        .await;
+
+    let list: Vec<Runtime> = match res {
+        Ok(response) if response.status().is_success() => {
+            match response.json().await {
+                Ok(list) => list,
+                Err(e) => {
+                    log::error("Piston Runtimes Parse", &e.to_string());
+                    return Default::default();
+                }
+            }
+        }
+        Ok(response) => {
+            log::error("Piston Runtimes", &format!("HTTP {}", response.status()));
+            return Default::default();
+        }
+        Err(e) => {
+            log::error("Piston Runtimes", &e.to_string());
+            return Default::default();
+        }
+    };
+
+    let mut map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    for runtime in list {
+        match map.get(&runtime.language) {
+            Some(existing) if !version_newer(&runtime.version, existing) => {}
+            _ => {
+                map.insert(runtime.language, runtime.version);
+            }
+        }
+    }
+    map
+}
+
+/// The latest available version for `language`, or `fallback` when the
+/// runtimes list couldn't be fetched or doesn't know the language
+async fn resolve_piston_version(language: &str, fallback: &str) -> String {
+    PISTON_RUNTIMES
+        .get_or_init(fetch_piston_runtimes)
+        .await
+        .get(language)
+        .cloned()
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// The in-tree `(piston_name, version, filename)` default for Python — the
+/// only runtime actually submitted, since every language converts to Python
+const DEFAULT_PYTHON_RUNTIME: (&str, &str, &str) = ("python", "3.10.0", "solution.py");
+
+/// Where the optional runtime-pinning file lives:
+/// `$XDG_CONFIG_HOME/babel/piston.toml` or `~/.config/babel/piston.toml`
+fn piston_config_path() -> Option<std::path::PathBuf> {
+    let base = std::env::var("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|_| {
+            std::env::var("HOME").map(|home| std::path::PathBuf::from(home).join(".config"))
+        })
+        .ok()?;
+    Some(base.join("babel").join("piston.toml"))
+}
+
+/// Pull `language`/`version`/`filename` from the `[section]` of a
+/// TOML-subset document (same `key = "value"` format llm.rs reads).
+/// Missing or empty keys come back `None` so each falls back individually.
+fn piston_override_from(
+    contents: &str,
+    section: &str,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let mut in_section = false;
+    let (mut language, mut version, mut filename) = (None, None, None);
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_section = header.trim() == section;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some((k, v)) = line.split_once('=') {
+            let v = v.trim().trim_matches('"').trim_matches('\'');
+            if v.is_empty() {
+                continue;
+            }
+            match k.trim() {
+                "language" => language = Some(v.to_string()),
+                "version" => version = Some(v.to_string()),
+                "filename" => filename = Some(v.to_string()),
+                _ => {}
+            }
+        }
+    }
+    (language, version, filename)
+}
+
+/// The `(piston_name, version, filename)` triple to submit for `section`,
+/// from `piston.toml` where present and the in-tree defaults otherwise:
+///
+/// ```toml
+/// [python]
+/// version = "3.12.0"
+/// ```
+///
+/// pins just the version, keeping the default name and filename. This lets
+/// a self-hosted Piston or newer emkc.org runtime be matched without
+/// recompiling.
+fn piston_runtime_for(section: &str) -> (String, String, String) {
+    let (def_lang, def_ver, def_file) = DEFAULT_PYTHON_RUNTIME;
+    let (language, version, filename) = piston_config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| piston_override_from(&contents, section))
+        .unwrap_or((None, None, None));
+    (
+        language.unwrap_or_else(|| def_lang.to_string()),
+        version.unwrap_or_else(|| def_ver.to_string()),
+        filename.unwrap_or_else(|| def_file.to_string()),
+    )
+}
+
+/// Marker prefix for a Piston 429; the suffix is the retry delay in seconds
+/// (from `Retry-After`, or [`PISTON_RATE_LIMIT_FALLBACK_SECS`] when the
+/// header is missing or unparseable)
+const PISTON_RATE_LIMITED_PREFIX: &str = "Piston rate limited, retry after ";
+const PISTON_RATE_LIMIT_FALLBACK_SECS: u64 = 30;
+
+/// Retry delay carried in a rate-limit error from the executor, if that is
+/// what `err` is
+fn rate_limited_retry_secs(err: &str) -> Option<u64> {
+    err.strip_prefix(PISTON_RATE_LIMITED_PREFIX)?.trim().parse().ok()
+}
+
+/// Real executor backed by the public Piston API
+struct HttpPistonExecutor;
+
+impl PistonExecutor for HttpPistonExecutor {
+    async fn execute(&self, request: &PistonRequest) -> Result<PistonResponse, String> {
+        // Negotiate the runtime version so upstream version churn on emkc.org
+        // doesn't surface as "Requested runtime is unknown"; the version in
+        // the incoming request is only the fallback
+        let mut request = request.clone();
+        request.version = resolve_piston_version(&request.language, &request.version).await;
+
+        let client = reqwest::Client::new();
+        let res = client.post("https://emkc.org/api/v2/piston/execute")
+            .json(&request)
+            .send()
+            .await;
+
+        match res {
+            Ok(response) => {
+                // emkc.org enforces a per-IP rate limit; answer 429 with a
+                // structured error so the caller can surface the retry delay
+                if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    let retry_secs = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|s| s.trim().parse::<u64>().ok())
+                        .unwrap_or(PISTON_RATE_LIMIT_FALLBACK_SECS);
+                    log::error(
+                        "Piston API",
+                        &format!("rate limited (429), retry in {}s", retry_secs),
+                    );
+                    return Err(format!("{}{}", PISTON_RATE_LIMITED_PREFIX, retry_secs));
+                }
+                if !response.status().is_success() {
+                    let status = response.status();
+                    let error_msg = format!("API Error: {}", status);
+
+                    // Try to get response body for detailed logging
+                    let body = response.text().await.unwrap_or_else(|_| "Could not read response body".to_string());
+                    log::error(
+                        "Piston API",
+                        &format!("{} (language: {}): {}", error_msg, request.language, body),
+                    );
+                    return Err(error_msg);
+                }
+
+                match response.json::<PistonResponse>().await {
+                    Ok(piston_res) => Ok(piston_res),
+                    Err(e) => {
+                        let error_msg = format!("Failed to parse Piston response: {}", e);
+                        log::error("Piston Response Parse", &error_msg);
+                        Err(error_msg)
+                    }
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Network Error: {}", e);
+                log::error("Piston Network", &error_msg);
+                Err(error_msg)
+            }
+        }
+    }
+}
+
+/// Test cases as the JSON objects the Python harness consumes, keyed by the
+/// declared parameter names so the harness can dispatch generically for
+/// problems it has no branch for
+fn test_cases_as_json(problem: &Problem) -> Vec<serde_json::Value> {
+    problem
+        .test_cases
+        .iter()
+        .map(|tc| {
+            let mut obj = serde_json::Map::new();
+            for (param, value) in problem.parameters.iter().zip(&tc.input) {
+                obj.insert(param.name.clone(), serde_json::Value::String(value.clone()));
+            }
+            obj.insert("expected".to_string(), serde_json::Value::String(tc.expected.clone()));
+            serde_json::Value::Object(obj)
+        })
+        .collect()
+}
+
+/// The exact Python harness that would be sent to Piston for `python_code`
+/// on `problem`. Public for the `harness` subcommand, which prints it for
+/// inspection without any network call.
+pub fn preview_python_harness(problem: &Problem, python_code: &str) -> String {
+    generate_python_harness(python_code, &test_cases_as_json(problem), problem)
+}
+
+/// Whether submissions in `language` can actually be executed. Only Python
+/// has a native Piston harness; every other language runs by being
+/// LLM-converted to Python first, so without a configured translation
+/// provider those languages cannot run at all.
+pub fn language_executable(language: Language) -> bool {
+    language == Language::Python
+        || crate::llm::gemini_key().is_some()
+        || crate::llm::openai_key().is_some()
+}
+
 /// Async test runner using Piston API
 pub async fn run_tests_on_piston(
-    code: String, 
-    problem: Problem, 
+    code: String,
+    problem: Problem,
     language: Language,
     tx: mpsc::Sender<ExecutionEvent>
 ) -> TestResults {
-    
+    run_tests_with_executor(code, problem, language, tx, &HttpPistonExecutor).await
+}
+
+async fn run_tests_with_executor<E: PistonExecutor>(
+    code: String,
+    problem: Problem,
+    language: Language,
+    tx: mpsc::Sender<ExecutionEvent>,
+    executor: &E,
+) -> TestResults {
+
     // Helper to send output
     let send_log = |text: String, is_error: bool| {
         let tx = tx.clone();
@@ -398,6 +794,18 @@ pub async fn run_tests_on_piston(
         });
     };
 
+    // A problem with no test cases should be rejected at load time
+    // (Problem::validate), but guard here too rather than reporting 0/0
+    if problem.test_cases.is_empty() {
+        send_log("No test cases defined for this problem".to_string(), true);
+        return TestResults {
+            total: 0,
+            passed: 0,
+            failed: 0,
+            details: Vec::new(),
+        };
+    }
+
     // Convert to Python if not already Python
     let python_code = if language != Language::Python {
         send_log(format!("Converting {} to Python...", language.display_name()), false);
@@ -422,46 +830,12 @@ pub async fn run_tests_on_piston(
 
     send_log("Preparing Python environment...".to_string(), false);
 
-    // Build test cases JSON
-    let test_cases_json: Vec<serde_json::Value> = problem
-        .test_cases
-        .iter()
-        .map(|tc| {
-            match problem.id {
-                1 => serde_json::json!({
-                    "nums": tc.input[0],
-                    "target": tc.input[1],
-                    "expected": tc.expected
-                }),
-                2 => serde_json::json!({
-                    "s": tc.input[0],
-                    "expected": tc.expected
-                }),
-                3 => serde_json::json!({
-                    "n": tc.input[0],
-                    "expected": tc.expected
-                }),
-                4 => serde_json::json!({
-                    "s": tc.input[0],
-                    "expected": tc.expected
-                }),
-                5 => serde_json::json!({
-                    "n": tc.input[0],
-                    "expected": tc.expected
-                }),
-                _ => serde_json::json!({
-                    "input": tc.input,
-                    "expected": tc.expected
-                })
-            }
-        })
-        .collect();
-
     // Always generate Python harness since we converted to Python
-    let full_code = generate_python_harness(&python_code, &test_cases_json);
+    let full_code = preview_python_harness(&problem, &python_code);
 
-    // Always use Python for Piston execution
-    let (piston_lang, piston_ver, filename) = ("python", "3.10.0", "solution.py");
+    // Always use Python for Piston execution; piston.toml may pin a
+    // different runtime (e.g. to match a self-hosted Piston)
+    let (piston_lang, piston_ver, filename) = piston_runtime_for("python");
 
     let request = PistonRequest {
         language: piston_lang.to_string(),
@@ -478,89 +852,177 @@ pub async fn run_tests_on_piston(
     log_piston_full_exchange(
         "Python (converted)",
         &full_code,
-        "[Request sent, awaiting response...]"
+        "[Request sent, awaiting response...]",
+        None
     );
 
-    let client = reqwest::Client::new();
-    let res = client.post("https://emkc.org/api/v2/piston/execute")
-        .json(&request)
-        .send()
-        .await;
+    let piston_start = std::time::Instant::now();
+    let res = executor.execute(&request).await;
+    let piston_elapsed = piston_start.elapsed();
 
     match res {
         Ok(response) => {
-            if !response.status().is_success() {
-                let status = response.status();
-                let error_msg = format!("API Error: {}", status);
-                
-                // Try to get response body for detailed logging
-                let body = response.text().await.unwrap_or_else(|_| "Could not read response body".to_string());
-                log_piston_error(
-                    language.display_name(),
-                    &format!("HTTP {}", status.as_u16()),
-                    &body
-                );
-                
-                log_error("Piston API", &error_msg);
-                send_log(error_msg.clone(), true);
-                return create_error_results(&problem, &error_msg);
-            }
-
-            match response.json::<PistonResponse>().await {
-                Ok(piston_res) => {
-                    // Log full response for debugging
-                    let response_json = serde_json::json!({
-                        "stdout": &piston_res.run.stdout,
-                        "stderr": &piston_res.run.stderr,
-                        "exit_code": &piston_res.run.code
-                    });
-                    log_piston_full_exchange(
-                        language.display_name(),
-                        "[See previous request]",
-                        &serde_json::to_string_pretty(&response_json).unwrap_or_default()
-                    );
-                    
-                    send_log("Execution completed.".to_string(), false);
-                    
-                    // Show stdout/stderr in the terminal window
-                    for line in piston_res.run.stdout.lines() {
-                        send_log(line.to_string(), false);
+            // A failed compile stage (compiled runtimes only) means the run
+            // output is meaningless: surface the compiler errors and score
+            // it as a distinct failure instead of "no output"
+            if let Some(compile) = &response.compile {
+                if compile.code.unwrap_or(0) != 0 {
+                    send_log("── Compilation failed ──".to_string(), true);
+                    for line in compile.stdout.lines().chain(compile.stderr.lines()) {
+                        send_log(strip_ansi_codes(line), true);
                     }
-                    for line in piston_res.run.stderr.lines() {
-                        send_log(line.to_string(), true);
+                    let _ = tx
+                        .send(ExecutionEvent::Debug {
+                            harness: full_code.clone(),
+                            raw_response: format!(
+                                "Compilation failed:\n{}\n{}",
+                                compile.stdout, compile.stderr
+                            ),
+                        })
+                        .await;
+                    return create_error_results(&problem, "Compilation failed");
+                }
+                // Successful compile with diagnostics (warnings) still gets shown
+                if !compile.stderr.trim().is_empty() {
+                    send_log("── Compiler output ──".to_string(), false);
+                    for line in compile.stderr.lines() {
+                        send_log(strip_ansi_codes(line), true);
                     }
+                }
+            }
+
+            let run = response.run;
+            // Log full response for debugging
+            let response_json = serde_json::json!({
+                "stdout": &run.stdout,
+                "stderr": &run.stderr,
+                "exit_code": &run.code
+            });
+            log_piston_full_exchange(
+                language.display_name(),
+                "[See previous request]",
+                &serde_json::to_string_pretty(&response_json).unwrap_or_default(),
+                Some(piston_elapsed)
+            );
+
+            send_log(format!("Completed in {:.1}s", piston_elapsed.as_secs_f32()), false);
+
+            // Same payload the log file gets, but kept in-app for the
+            // verbose overlay
+            let _ = tx
+                .send(ExecutionEvent::Debug {
+                    harness: full_code.clone(),
+                    raw_response: serde_json::to_string_pretty(&response_json).unwrap_or_default(),
+                })
+                .await;
 
-                    // Parse JSON results from stdout
-                    parse_results(&piston_res.run.stdout, &problem)
+            // Show the user's own stdout (everything before the results marker)
+            // as a labeled section, separate from the parsed results
+            let program_output = match run.stdout.find(RESULTS_START_MARKER) {
+                Some(idx) => &run.stdout[..idx],
+                None => run.stdout.as_str(),
+            };
+            if !program_output.trim().is_empty() {
+                send_log("── Program output ──".to_string(), false);
+                for line in program_output.lines() {
+                    send_log(strip_ansi_codes(line), false);
                 }
-                Err(e) => {
-                    let error_msg = format!("Failed to parse Piston response: {}", e);
-                    log_error("Piston Response Parse", &error_msg);
-                    send_log(error_msg.clone(), true);
-                    create_error_results(&problem, &format!("Parse Error: {}", e))
+            }
+            for line in run.stderr.lines() {
+                send_log(strip_ansi_codes(line), true);
+            }
+
+            if let Some(code) = run.code {
+                if code != 0 {
+                    send_log(format!("Process exited with code {}", code), true);
                 }
             }
+
+            // Parse JSON results from stdout
+            parse_results(&run.stdout, run.code, &problem)
         }
-        Err(e) => {
-            let error_msg = format!("Network Error: {}", e);
-            log_error("Piston Network", &error_msg);
+        Err(error_msg) => {
+            let _ = tx
+                .send(ExecutionEvent::Debug {
+                    harness: full_code.clone(),
+                    raw_response: error_msg.clone(),
+                })
+                .await;
+            // A 429 gets a readable message plus a client-side cooldown so
+            // retries don't keep extending the per-IP ban upstream
+            if let Some(retry_secs) = rate_limited_retry_secs(&error_msg) {
+                let _ = tx.send(ExecutionEvent::RateLimited(retry_secs)).await;
+                let message = format!("Rate limited — retry in {}s", retry_secs);
+                send_log(message.clone(), true);
+                return create_error_results(&problem, &message);
+            }
             send_log(error_msg.clone(), true);
-            create_error_results(&problem, &format!("Network Error: {}", e))
+            create_error_results(&problem, &error_msg)
         }
     }
 }
 
-fn generate_python_harness(user_code: &str, test_cases: &[serde_json::Value]) -> String {
+/// Strip ANSI escape sequences (colored compiler output etc.) so they don't
+/// render as literal garbage in the output panel
+fn strip_ansi_codes(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                // CSI sequence: skip parameter bytes until the final byte
+                for next in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&next) {
+                        break;
+                    }
+                }
+            } else {
+                // Two-character escape (e.g. ESC c)
+                chars.next();
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// Sentinel markers so scoring survives arbitrary user prints to stdout
+const RESULTS_START_MARKER: &str = "__BABEL_RESULTS_START__";
+const RESULTS_END_MARKER: &str = "__BABEL_RESULTS_END__";
+
+fn generate_python_harness(user_code: &str, test_cases: &[serde_json::Value], problem: &Problem) -> String {
     format!(
         r#"
 import json
 import sys
 
-# User's code
-{}
+# User's code is embedded as a string literal and exec'd into its own
+# namespace, so user symbols (e.g. a variable named `results` or
+# `test_cases`) can't collide with the harness internals below
+_babel_user_code = {}
+_babel_user_ns = {{}}
+exec(compile(_babel_user_code, "<user_code>", "exec"), _babel_user_ns)
 
 # Test runner
 test_cases = {}
+RETURN_TYPE = "{}"
+FLOAT_TOLERANCE = {}
+PROBLEM_ID = {}
+PARAMETERS = {}
+FUNCTION_NAMES = {}
+
+def floats_equal(actual, expected):
+    try:
+        if isinstance(actual, list) and isinstance(expected, list):
+            return len(actual) == len(expected) and all(
+                abs(float(a) - float(b)) <= FLOAT_TOLERANCE
+                for a, b in zip(actual, expected)
+            )
+        return abs(float(actual) - float(expected)) <= FLOAT_TOLERANCE
+    except (TypeError, ValueError):
+        return False
 
 def parse_value(value):
     if not isinstance(value, str):
@@ -570,6 +1032,25 @@ def parse_value(value):
     except Exception:
         return value
 
+def coerce_param(value, param_type):
+    value = parse_value(value)
+    # char[] inputs may arrive as a JSON array or a plain string
+    if param_type == "char[]" and isinstance(value, str):
+        return list(value)
+    if param_type == "int" and not isinstance(value, int):
+        return int(value)
+    return value
+
+def user_fn(*names):
+    for name in names:
+        fn = _babel_user_ns.get(name)
+        if callable(fn):
+            return fn
+    return None
+
+def find_function():
+    return user_fn(*FUNCTION_NAMES)
+
 results = []
 for i, tc in enumerate(test_cases):
     try:
@@ -577,17 +1058,31 @@ for i, tc in enumerate(test_cases):
         expected = None
         
         # Dynamically handle different problem types
-        if "nums" in tc and "target" in tc:
+        if PROBLEM_ID not in (1, 2, 3, 4, 5):
+            # Generic dispatch from the declared signature; char[] values
+            # are converted between str and list as needed
+            expected = parse_value(tc["expected"])
+            args = [coerce_param(tc[p["name"]], p["param_type"]) for p in PARAMETERS]
+            fn = find_function()
+            if fn is not None:
+                result = fn(*args)
+                char_params = [i for i, p in enumerate(PARAMETERS) if p["param_type"] == "char[]"]
+                if result is None and char_params:
+                    # In-place convention: result is the mutated char[] arg
+                    result = args[char_params[0]]
+                actual = result
+                if RETURN_TYPE == "char[]" and isinstance(actual, str) and isinstance(expected, list):
+                    actual = list(actual)
+        elif "nums" in tc and "target" in tc:
             # Two Sum (problem 1)
             nums = parse_value(tc["nums"])
             target = int(parse_value(tc["target"]))
             expected = parse_value(tc["expected"])
             
             # Try finding solution function
-            if 'two_sum' in dir():
-                actual = two_sum(nums, target)
-            elif 'twoSum' in dir():
-                actual = twoSum(nums, target)
+            fn = user_fn('two_sum', 'twoSum')
+            if fn is not None:
+                actual = fn(nums, target)
         
         elif "s" in tc:
             # String problems (problem 2 or 4)
@@ -597,11 +1092,9 @@ for i, tc in enumerate(test_cases):
             if isinstance(s_input, list):
                 # Reverse String (problem 2) - modifies in place OR returns result
                 s_copy = s_input.copy()
-                if 'reverse_string' in dir():
-                    result = reverse_string(s_copy)
-                    actual = result if result is not None else s_copy
-                elif 'reverseString' in dir():
-                    result = reverseString(s_copy)
+                fn = user_fn('reverse_string', 'reverseString')
+                if fn is not None:
+                    result = fn(s_copy)
                     actual = result if result is not None else s_copy
                 
                 # Handle case where function returns a string instead of a list
@@ -609,10 +1102,9 @@ for i, tc in enumerate(test_cases):
                     actual = list(actual)
             else:
                 # Palindrome check (problem 4)
-                if 'is_palindrome' in dir():
-                    actual = is_palindrome(s_input)
-                elif 'isPalindrome' in dir():
-                    actual = isPalindrome(s_input)
+                fn = user_fn('is_palindrome', 'isPalindrome')
+                if fn is not None:
+                    actual = fn(s_input)
         
         elif "n" in tc:
             # Number problems (problem 3 or 5)
@@ -621,23 +1113,22 @@ for i, tc in enumerate(test_cases):
             
             if isinstance(expected, list):
                 # Fizz Buzz (problem 3)
-                if 'fizz_buzz' in dir():
-                    actual = fizz_buzz(n)
-                elif 'fizzBuzz' in dir():
-                    actual = fizzBuzz(n)
+                fn = user_fn('fizz_buzz', 'fizzBuzz')
             else:
                 # Fibonacci (problem 5)
-                if 'fibonacci' in dir():
-                    actual = fibonacci(n)
-                elif 'fib' in dir():
-                    actual = fib(n)
+                fn = user_fn('fibonacci', 'fib')
+            if fn is not None:
+                actual = fn(n)
         
         if actual is None:
             results.append({{"passed": False, "actual": "Error: No function found"}})
         else:
             # Compare results
             passed = False
-            if isinstance(actual, list) and isinstance(expected, list):
+            if RETURN_TYPE in ("float", "float[]"):
+                # Epsilon compare so 0.1+0.2-style rounding doesn't fail
+                passed = floats_equal(actual, expected)
+            elif isinstance(actual, list) and isinstance(expected, list):
                 # For array results, sort before comparison if they're numeric
                 if len(actual) > 0 and isinstance(actual[0], (int, float)):
                     passed = sorted(actual) == sorted(expected)
@@ -651,17 +1142,67 @@ for i, tc in enumerate(test_cases):
     except Exception as e:
         results.append({{"passed": False, "actual": f"Error: {{e}}"}})
 
-print(json.dumps(results))
+print("{}" + json.dumps(results) + "{}")
 "#,
-        user_code,
-        serde_json::to_string(test_cases).unwrap_or_default()
+        // A JSON string is a valid Python string literal, so this survives
+        // quotes, backslashes and triple-quote sequences in user code
+        serde_json::to_string(user_code).unwrap_or_default(),
+        serde_json::to_string(test_cases).unwrap_or_default(),
+        problem.return_type,
+        problem.float_tolerance.unwrap_or(DEFAULT_FLOAT_TOLERANCE),
+        problem.id,
+        serde_json::to_string(&problem.parameters).unwrap_or_default(),
+        serde_json::to_string(&[
+            problem.function_name.clone(),
+            to_camel_case(&problem.function_name),
+        ])
+        .unwrap_or_default(),
+        RESULTS_START_MARKER,
+        RESULTS_END_MARKER
     )
 }
 
-fn parse_results(stdout: &str, problem: &Problem) -> TestResults {
-    // Find the last line that looks like a JSON array
-    let json_line = stdout.lines().rev().find(|l| l.trim().starts_with('['));
-    
+/// Host-side mirror of the harness comparison: epsilon-based for
+/// `float`/`float[]` return types, exact equality otherwise. Used when
+/// results are re-checked outside the harness.
+pub(crate) fn compare_values(actual: &str, expected: &str, return_type: &str, tolerance: f64) -> bool {
+    if return_type != "float" && return_type != "float[]" {
+        return actual == expected;
+    }
+
+    // Accept both a bare number and a JSON array of numbers
+    let parse = |s: &str| -> Option<Vec<f64>> {
+        match serde_json::from_str::<serde_json::Value>(s.trim()).ok()? {
+            serde_json::Value::Array(items) => items.iter().map(|v| v.as_f64()).collect(),
+            value => Some(vec![value.as_f64()?]),
+        }
+    };
+
+    match (parse(actual), parse(expected)) {
+        (Some(a), Some(b)) => {
+            a.len() == b.len() && a.iter().zip(&b).all(|(x, y)| (x - y).abs() <= tolerance)
+        }
+        _ => false,
+    }
+}
+
+/// Extract the results JSON from harness stdout. Prefers the sentinel markers;
+/// falls back to the last JSON-array-looking line for older harness output.
+fn extract_results_json(stdout: &str) -> Option<&str> {
+    if let Some(start) = stdout.find(RESULTS_START_MARKER) {
+        let after_start = &stdout[start + RESULTS_START_MARKER.len()..];
+        if let Some(end) = after_start.find(RESULTS_END_MARKER) {
+            return Some(&after_start[..end]);
+        }
+    }
+
+    // Fallback: find the last line that looks like a JSON array
+    stdout.lines().rev().find(|l| l.trim().starts_with('['))
+}
+
+fn parse_results(stdout: &str, exit_code: Option<i32>, problem: &Problem) -> TestResults {
+    let json_line = extract_results_json(stdout);
+
     if let Some(line) = json_line {
         if let Ok(json_results) = serde_json::from_str::<Vec<serde_json::Value>>(line) {
              let details: Vec<TestResult> = problem
@@ -670,9 +1211,16 @@ fn parse_results(stdout: &str, problem: &Problem) -> TestResults {
                     .enumerate()
                     .map(|(i, tc)| {
                         let result = json_results.get(i);
-                        let passed = result.and_then(|r| r.get("passed")).and_then(|p| p.as_bool()).unwrap_or(false);
+                        let mut passed = result.and_then(|r| r.get("passed")).and_then(|p| p.as_bool()).unwrap_or(false);
                         let actual = result.and_then(|r| r.get("actual")).and_then(|a| a.as_str()).unwrap_or("Error").to_string();
 
+                        // Float results failed by an exact-comparing (older)
+                        // harness get a second chance host-side
+                        if !passed && matches!(problem.return_type.as_str(), "float" | "float[]") {
+                            let tolerance = problem.float_tolerance.unwrap_or(DEFAULT_FLOAT_TOLERANCE);
+                            passed = compare_values(&actual, &tc.expected, &problem.return_type, tolerance);
+                        }
+
                         TestResult {
                             case_number: i + 1,
                             passed,
@@ -694,7 +1242,14 @@ fn parse_results(stdout: &str, problem: &Problem) -> TestResults {
         }
     }
     
-    create_error_results(problem, "Failed to parse test results from output")
+    // No parseable results: the exit status tells us whether the program
+    // crashed before printing them or exited cleanly without saying anything
+    match exit_code {
+        Some(code) if code != 0 => {
+            create_error_results(problem, &format!("Runtime error (exit code {})", code))
+        }
+        _ => create_error_results(problem, "No output — solution produced no results"),
+    }
 }
 
 fn create_error_results(problem: &Problem, error: &str) -> TestResults {
@@ -717,7 +1272,7 @@ fn create_error_results(problem: &Problem, error: &str) -> TestResults {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TestResults {
     pub total: usize,
     pub passed: usize,
@@ -725,7 +1280,19 @@ pub struct TestResults {
     pub details: Vec<TestResult>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl TestResults {
+    /// Percent of cases passed. An empty run scores 0 rather than dividing
+    /// by zero (a zero-test-case problem should be caught at load time, but
+    /// the scoring math stays defensive regardless).
+    pub fn score_percent(&self) -> u8 {
+        self.passed
+            .saturating_mul(100)
+            .checked_div(self.total)
+            .unwrap_or(0) as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TestResult {
     pub case_number: usize,
     pub passed: bool,
@@ -733,3 +1300,253 @@ pub struct TestResult {
     pub expected: String,
     pub actual: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mock executor that returns canned stdout without touching the network
+    struct MockPistonExecutor {
+        stdout: String,
+    }
+
+    impl PistonExecutor for MockPistonExecutor {
+        async fn execute(&self, _request: &PistonRequest) -> Result<PistonResponse, String> {
+            Ok(PistonResponse {
+                run: PistonRunResult {
+                    stdout: self.stdout.clone(),
+                    stderr: String::new(),
+                    code: Some(0),
+                },
+                compile: None,
+            })
+        }
+    }
+
+    #[test]
+    fn harness_embeds_user_code_and_test_cases() {
+        let user_code = "def fib(n):\n    return n";
+        let test_cases = vec![serde_json::json!({ "n": "2", "expected": "1" })];
+        let harness = generate_python_harness(user_code, &test_cases, &Problem::fibonacci());
+
+        // User code is embedded as a string literal and exec'd into a
+        // separate namespace, so it appears JSON-escaped rather than verbatim
+        assert!(harness.contains(r#""def fib(n):\n    return n""#));
+        assert!(harness.contains("_babel_user_ns"));
+        assert!(harness.contains(r#""n": "2""#) || harness.contains(r#""n":"2""#));
+        assert!(harness.contains(RESULTS_START_MARKER));
+        assert!(harness.contains(RESULTS_END_MARKER));
+    }
+
+    /// There is no Go/Java `parseIntArray` to harden in this tree — every
+    /// submission is converted to Python and run through the Python harness,
+    /// whose inputs go through `json.loads` (negatives, spaces after commas
+    /// and arbitrary-size integers are all fine there). This pins the
+    /// negative/spaced case so a future hand-rolled parser can't regress it.
+    #[test]
+    fn harness_embeds_negative_and_spaced_array_inputs() {
+        let problem = Problem::two_sum();
+        // The built-in negative-number case is still present
+        assert!(problem
+            .test_cases
+            .iter()
+            .any(|tc| tc.input.iter().any(|v| v == "[-1,-2,-3,-4,-5]")));
+
+        let test_cases = vec![serde_json::json!({
+            "nums": "[ -1, -2 , -3 ]",
+            "target": "-5",
+            "expected": "[1,2]",
+        })];
+        let harness = generate_python_harness(
+            "def two_sum(nums, target):\n    return []",
+            &test_cases,
+            &problem,
+        );
+
+        // Inputs survive embedding verbatim and are parsed as JSON, not split
+        // on commas by hand
+        assert!(harness.contains("[ -1, -2 , -3 ]"));
+        assert!(harness.contains(r#""target": "-5""#) || harness.contains(r#""target":"-5""#));
+        assert!(harness.contains("json.loads"));
+    }
+
+    #[test]
+    fn piston_toml_overrides_fall_back_per_key() {
+        let contents = r#"
+# pin only the version; name and filename keep their defaults
+[python]
+version = "3.12.0"
+
+[rust]
+language = "rust"
+version = "1.74.0"
+filename = ""
+"#;
+        let (language, version, filename) = piston_override_from(contents, "python");
+        assert_eq!(language, None);
+        assert_eq!(version.as_deref(), Some("3.12.0"));
+        assert_eq!(filename, None);
+
+        // Empty values count as missing rather than submitting a blank field
+        let (language, version, filename) = piston_override_from(contents, "rust");
+        assert_eq!(language.as_deref(), Some("rust"));
+        assert_eq!(version.as_deref(), Some("1.74.0"));
+        assert_eq!(filename, None);
+
+        // No section at all: everything falls back
+        let (language, version, filename) = piston_override_from(contents, "go");
+        assert!(language.is_none() && version.is_none() && filename.is_none());
+    }
+
+    #[test]
+    fn compare_values_accepts_near_equal_floats() {
+        // 0.1 + 0.2 in IEEE 754
+        assert!(compare_values("0.30000000000000004", "0.3", "float", DEFAULT_FLOAT_TOLERANCE));
+        assert!(compare_values("[0.30000000000000004, 1.0]", "[0.3, 1]", "float[]", DEFAULT_FLOAT_TOLERANCE));
+
+        // Differences beyond the tolerance still fail
+        assert!(!compare_values("0.301", "0.3", "float", DEFAULT_FLOAT_TOLERANCE));
+        assert!(!compare_values("[0.3]", "[0.3, 0.3]", "float[]", DEFAULT_FLOAT_TOLERANCE));
+
+        // A looser per-problem tolerance widens the window
+        assert!(compare_values("0.301", "0.3", "float", 0.01));
+
+        // Non-float types keep exact comparison
+        assert!(!compare_values("0.30000000000000004", "0.3", "int", DEFAULT_FLOAT_TOLERANCE));
+    }
+
+    #[test]
+    fn harness_uses_epsilon_compare_for_float_problems() {
+        let mut problem = Problem::fibonacci();
+        problem.return_type = "float".to_string();
+        problem.float_tolerance = Some(1e-9);
+        let harness = generate_python_harness("def fib(n):\n    return n", &[], &problem);
+
+        assert!(harness.contains("RETURN_TYPE = \"float\""));
+        assert!(harness.contains("FLOAT_TOLERANCE = 0.000000001"));
+        assert!(harness.contains("floats_equal(actual, expected)"));
+    }
+
+    #[test]
+    fn error_messages_with_quotes_survive_the_results_pipeline() {
+        // The harness serializes results with json.dumps, so a solution
+        // throwing `Error: unexpected "token" at \path\` must come back as
+        // valid JSON rather than breaking the parse and scoring everything
+        // as a parse error
+        let message = r#"Error: unexpected "token" at \path\"#;
+        let canned = serde_json::json!([
+            { "passed": false, "actual": message },
+        ]);
+        let stdout = format!(
+            "{}{}{}\n",
+            RESULTS_START_MARKER, canned, RESULTS_END_MARKER
+        );
+
+        let mut problem = Problem::fibonacci();
+        problem.test_cases.truncate(1);
+        let results = parse_results(&stdout, Some(0), &problem);
+
+        assert_eq!(results.passed, 0);
+        assert_eq!(results.details[0].actual, message);
+    }
+
+    #[test]
+    fn exit_code_classifies_unparseable_output() {
+        let mut problem = Problem::fibonacci();
+        problem.test_cases.truncate(1);
+
+        // A crash before the results marker is a runtime error, not a
+        // generic parse failure
+        let crashed = parse_results("Traceback (most recent call last):", Some(1), &problem);
+        assert_eq!(crashed.passed, 0);
+        assert!(crashed.details[0].actual.contains("exit code 1"));
+
+        // A clean exit with nothing printed is its own story
+        let silent = parse_results("", Some(0), &problem);
+        assert!(silent.details[0].actual.contains("No output"));
+    }
+
+    #[test]
+    fn ansi_codes_are_stripped_from_output() {
+        let colored = "\x1b[1m\x1b[31merror[E0308]\x1b[0m: mismatched types";
+        assert_eq!(strip_ansi_codes(colored), "error[E0308]: mismatched types");
+        assert_eq!(strip_ansi_codes("plain text"), "plain text");
+    }
+
+    #[test]
+    fn function_names_follow_language_conventions() {
+        let problem = Problem::two_sum();
+        assert_eq!(problem.function_name_for(Language::Python), "two_sum");
+        assert_eq!(problem.function_name_for(Language::Rust), "two_sum");
+        assert_eq!(problem.function_name_for(Language::JavaScript), "twoSum");
+        assert_eq!(problem.function_name_for(Language::Java), "twoSum");
+    }
+
+    #[test]
+    fn results_are_extracted_despite_user_prints() {
+        let stdout = format!(
+            "[debug] my own bracket line\nhello\n{}[{{\"passed\": true, \"actual\": \"1\"}}]{}\n",
+            RESULTS_START_MARKER, RESULTS_END_MARKER
+        );
+        let json = extract_results_json(&stdout).expect("markers should be found");
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0]["passed"], true);
+    }
+
+    #[tokio::test]
+    async fn parses_results_including_partial_failures() {
+        let problem = Problem::fibonacci(); // 4 test cases
+        let canned = serde_json::json!([
+            { "passed": true, "actual": "1" },
+            { "passed": true, "actual": "2" },
+            { "passed": false, "actual": "7" },
+            { "passed": true, "actual": "55" },
+        ]);
+        let mock = MockPistonExecutor {
+            stdout: format!("{}\n", canned),
+        };
+
+        let (tx, _rx) = mpsc::channel(32);
+        let results = run_tests_with_executor(
+            "def fib(n):\n    return n".to_string(),
+            problem,
+            Language::Python,
+            tx,
+            &mock,
+        )
+        .await;
+
+        assert_eq!(results.total, 4);
+        assert_eq!(results.passed, 3);
+        assert_eq!(results.failed, 1);
+        assert!(!results.details[2].passed);
+        assert_eq!(results.details[2].actual, "7");
+    }
+
+    #[tokio::test]
+    async fn mock_error_produces_error_results() {
+        struct FailingExecutor;
+        impl PistonExecutor for FailingExecutor {
+            async fn execute(&self, _request: &PistonRequest) -> Result<PistonResponse, String> {
+                Err("Network Error: simulated".to_string())
+            }
+        }
+
+        let problem = Problem::fizz_buzz();
+        let total = problem.test_cases.len();
+        let (tx, _rx) = mpsc::channel(32);
+        let results = run_tests_with_executor(
+            "def fizz_buzz(n):\n    return []".to_string(),
+            problem,
+            Language::Python,
+            tx,
+            &FailingExecutor,
+        )
+        .await;
+
+        assert_eq!(results.passed, 0);
+        assert_eq!(results.failed, total);
+        assert!(results.details[0].actual.contains("Network Error"));
+    }
+}
@@ -1,18 +1,33 @@
+//! Syntax highlighting for the editor. With the default
+//! `syntect-highlighting` feature this is backed by [`syntect`] (hence the
+//! `SyntectHighlighter` name); without it a lightweight keyword tokenizer
+//! takes over so the dependency isn't forced on everyone. The syntax and
+//! theme sets are loaded once; per-line results are memoized by
+//! [`HighlightCache`].
+
 use ratatui::style::{Color, Style};
 use ratatui::text::Span;
+use std::collections::HashMap;
+#[cfg(feature = "syntect-highlighting")]
 use syntect::easy::HighlightLines;
+#[cfg(feature = "syntect-highlighting")]
 use syntect::highlighting::{Style as SyntectStyle, ThemeSet};
+#[cfg(feature = "syntect-highlighting")]
 use syntect::parsing::SyntaxSet;
+#[cfg(feature = "syntect-highlighting")]
 use once_cell::sync::Lazy;
 
 use crate::languages::Language;
 
 // Global syntax set and theme - loaded once
+#[cfg(feature = "syntect-highlighting")]
 static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(|| SyntaxSet::load_defaults_newlines());
+#[cfg(feature = "syntect-highlighting")]
 static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
 
 pub struct SyntectHighlighter;
 
+#[cfg(feature = "syntect-highlighting")]
 impl SyntectHighlighter {
     /// Highlight a line of code using syntect
     pub fn highlight(line: &str, language: &Language) -> Vec<Span<'static>> {
@@ -80,7 +95,199 @@ impl SyntectHighlighter {
     }
 }
 
+#[cfg(not(feature = "syntect-highlighting"))]
+impl SyntectHighlighter {
+    /// Fallback keyword tokenizer: comments, string literals, numbers and a
+    /// cross-language keyword list. Far less accurate than syntect but has
+    /// no grammar dependencies at all.
+    pub fn highlight(line: &str, language: &Language) -> Vec<Span<'static>> {
+        const KEYWORDS: &[&str] = &[
+            "fn", "let", "mut", "pub", "return", "if", "else", "elif", "for",
+            "while", "match", "def", "function", "func", "fun", "val", "var",
+            "const", "class", "import", "use", "do", "end", "then", "in",
+            "case", "of", "type", "struct", "enum", "impl", "public",
+            "private", "static", "void", "new", "nil", "None", "null",
+            "true", "false", "True", "False",
+        ];
+
+        fn comment_prefix(language: &Language) -> &'static str {
+            match language {
+                Language::Python | Language::Elixir => "#",
+                Language::Haskell | Language::Lua => "--",
+                Language::OCaml => "(*",
+                _ => "//",
+            }
+        }
+
+        fn flush_word(word: &mut String, spans: &mut Vec<Span<'static>>) {
+            if word.is_empty() {
+                return;
+            }
+            let style = if KEYWORDS.contains(&word.as_str()) {
+                Style::default().fg(Color::Magenta)
+            } else if word.starts_with(|c: char| c.is_ascii_digit()) {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(word.clone(), style));
+            word.clear();
+        }
+
+        let (code, comment) = match line.find(comment_prefix(language)) {
+            Some(idx) => (&line[..idx], Some(&line[idx..])),
+            None => (line, None),
+        };
+
+        let mut spans = Vec::new();
+        let mut word = String::new();
+        let mut chars = code.chars();
+        while let Some(c) = chars.next() {
+            if c == '"' || c == '\'' {
+                flush_word(&mut word, &mut spans);
+                let mut literal = String::from(c);
+                for c2 in chars.by_ref() {
+                    literal.push(c2);
+                    if c2 == c {
+                        break;
+                    }
+                }
+                spans.extend(string_literal_spans(&literal, language));
+            } else if c.is_alphanumeric() || c == '_' {
+                word.push(c);
+            } else {
+                flush_word(&mut word, &mut spans);
+                spans.push(Span::raw(c.to_string()));
+            }
+        }
+        flush_word(&mut word, &mut spans);
+
+        if let Some(comment) = comment {
+            spans.push(Span::styled(
+                comment.to_string(),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        if spans.is_empty() {
+            spans.push(Span::raw(String::new()));
+        }
+        spans
+    }
+}
+
+/// Interpolation delimiters per language, e.g. `${expr}` in JS/TS and
+/// `{expr}` in Python f-strings. None means strings are rendered flat.
+#[cfg(not(feature = "syntect-highlighting"))]
+fn interpolation_delims(language: &Language) -> Option<(&'static str, char)> {
+    match language {
+        Language::JavaScript | Language::TypeScript | Language::Kotlin => Some(("${", '}')),
+        Language::Python => Some(("{", '}')),
+        Language::Elixir => Some(("#{", '}')),
+        Language::Swift => Some(("\\(", ')')),
+        _ => None,
+    }
+}
+
+/// Break one string literal (quotes included) into spans: the string body in
+/// green, escape sequences (`\n`, `\"`) highlighted, and interpolated
+/// expressions rendered with code colors between distinct delimiters.
+#[cfg(not(feature = "syntect-highlighting"))]
+fn string_literal_spans(literal: &str, language: &Language) -> Vec<Span<'static>> {
+    let string_style = Style::default().fg(Color::Green);
+    let escape_style = Style::default().fg(Color::Yellow);
+    let delim_style = Style::default().fg(Color::Cyan);
+
+    let delims = match interpolation_delims(language) {
+        Some(delims) => delims,
+        None => return vec![Span::styled(literal.to_string(), string_style)],
+    };
+    let (open, close) = delims;
+
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = literal;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(plain.clone(), string_style));
+            plain.clear();
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        // Interpolation first: Swift's `\(` would otherwise read as an escape
+        if rest.starts_with(open) {
+            if let Some(end) = rest.find(close) {
+                flush(&mut plain, &mut spans);
+                let inner = &rest[open.len()..end];
+                spans.push(Span::styled(open.to_string(), delim_style));
+                spans.push(Span::raw(inner.to_string()));
+                spans.push(Span::styled(close.to_string(), delim_style));
+                rest = &rest[end + close.len_utf8()..];
+                continue;
+            }
+        }
+        if c == '\\' {
+            let mut indices = rest.char_indices();
+            indices.next();
+            if let Some((idx, escaped)) = indices.next() {
+                flush(&mut plain, &mut spans);
+                let end = idx + escaped.len_utf8();
+                spans.push(Span::styled(rest[..end].to_string(), escape_style));
+                rest = &rest[end..];
+                continue;
+            }
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+// Past this many distinct lines the cache is dropped and rebuilt, so a long
+// session can't grow it unbounded
+const CACHE_CAP: usize = 4096;
+
+/// Memoizes per-line highlight results so unchanged lines don't re-run
+/// syntect on every frame. Keyed by line content; cleared when the language
+/// swaps (the same text highlights differently) or the cap is hit.
+pub struct HighlightCache {
+    language: Language,
+    cache: HashMap<String, Vec<Span<'static>>>,
+}
+
+impl HighlightCache {
+    pub fn new(language: Language) -> Self {
+        HighlightCache {
+            language,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Cached equivalent of `SyntectHighlighter::highlight`
+    pub fn spans(&mut self, line: &str, language: Language) -> Vec<Span<'static>> {
+        if language != self.language {
+            self.cache.clear();
+            self.language = language;
+        }
+
+        if let Some(spans) = self.cache.get(line) {
+            return spans.clone();
+        }
+
+        let spans = SyntectHighlighter::highlight(line, &language);
+        if self.cache.len() >= CACHE_CAP {
+            self.cache.clear();
+        }
+        self.cache.insert(line.to_string(), spans.clone());
+        spans
+    }
+}
+
 /// Convert syntect style to ratatui style
+#[cfg(feature = "syntect-highlighting")]
 fn syntect_to_ratatui_style(style: SyntectStyle) -> Style {
     let fg = Color::Rgb(style.foreground.r, style.foreground.g, style.foreground.b);
     Style::default().fg(fg)
@@ -0,0 +1,208 @@
+use ratatui::style::Color;
+
+/// What the terminal can actually display. Detected from `COLORTERM`/`TERM`
+/// at startup; `BABEL_COLOR=16|256|truecolor` overrides detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Ansi16,
+    Xterm256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    pub fn detect() -> Self {
+        if let Ok(forced) = std::env::var("BABEL_COLOR") {
+            match forced.to_lowercase().as_str() {
+                "16" | "ansi" => return ColorDepth::Ansi16,
+                "256" => return ColorDepth::Xterm256,
+                "truecolor" | "24bit" => return ColorDepth::TrueColor,
+                _ => {}
+            }
+        }
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+            return ColorDepth::TrueColor;
+        }
+        if std::env::var("TERM").unwrap_or_default().contains("256color") {
+            return ColorDepth::Xterm256;
+        }
+        ColorDepth::Ansi16
+    }
+}
+
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// Nearest entry in the xterm 256-color table: the 6x6x6 cube (16-231)
+/// or the grayscale ramp (232-255), whichever is closer
+fn nearest_xterm256(r: u8, g: u8, b: u8) -> u8 {
+    const STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_step = |c: u8| -> usize {
+        STEPS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| (c as i32 - **s as i32).abs())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+
+    let (ri, gi, bi) = (nearest_step(r), nearest_step(g), nearest_step(b));
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (STEPS[ri], STEPS[gi], STEPS[bi]);
+
+    let gray_level = ((r as u32 + g as u32 + b as u32) / 3) as i32;
+    let gray_i = ((gray_level - 8) / 10).clamp(0, 23) as usize;
+    let gray_value = (8 + 10 * gray_i) as u8;
+    let gray_index = 232 + gray_i;
+    let gray_rgb = (gray_value, gray_value, gray_value);
+
+    if color_distance((r, g, b), gray_rgb) < color_distance((r, g, b), cube_rgb) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Nearest of the 16 basic ANSI colors (standard xterm default values)
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| color_distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+/// Degrade an RGB value to what the terminal can show. Named colors are
+/// already depth-safe and pass through untouched.
+fn adapt_color(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (Color::Rgb(_, _, _), ColorDepth::TrueColor) => color,
+        (Color::Rgb(r, g, b), ColorDepth::Xterm256) => Color::Indexed(nearest_xterm256(r, g, b)),
+        (Color::Rgb(r, g, b), ColorDepth::Ansi16) => nearest_ansi16(r, g, b),
+        _ => color,
+    }
+}
+
+/// Centralized color palette so renderers don't hardcode `Color::Rgb` values.
+/// Selected once at startup via the `BABEL_THEME` env var.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub gold: Color,       // titles and top-score accents
+    pub bronze: Color,     // panel borders
+    pub purple: Color,     // focused accents (editor border, shortcuts)
+    pub purple_dim: Color, // unfocused variant of purple
+    pub amber: Color,      // section labels
+    pub text: Color,
+    pub text_dim: Color,
+    pub text_faint: Color,
+    pub success: Color,
+    pub warn: Color,
+    pub error: Color,
+}
+
+impl Theme {
+    /// The original "Terminal of Babel" bronze/gold/purple palette
+    pub fn babel() -> Self {
+        Theme {
+            gold: Color::Rgb(255, 191, 0),
+            bronze: Color::Rgb(139, 90, 43),
+            purple: Color::Rgb(147, 112, 219),
+            purple_dim: Color::Rgb(90, 70, 130),
+            amber: Color::Rgb(180, 140, 80),
+            text: Color::Rgb(220, 220, 220),
+            text_dim: Color::Rgb(180, 180, 180),
+            text_faint: Color::Rgb(140, 140, 140),
+            success: Color::Rgb(100, 200, 130),
+            warn: Color::Rgb(255, 200, 80),
+            error: Color::Rgb(255, 100, 100),
+        }
+    }
+
+    /// Maximum-contrast palette for low-vision setups
+    pub fn high_contrast() -> Self {
+        Theme {
+            gold: Color::Yellow,
+            bronze: Color::White,
+            purple: Color::Cyan,
+            purple_dim: Color::Blue,
+            amber: Color::Yellow,
+            text: Color::White,
+            text_dim: Color::White,
+            text_faint: Color::Gray,
+            success: Color::Green,
+            warn: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    /// Grayscale palette; state is conveyed by brightness only
+    pub fn monochrome() -> Self {
+        Theme {
+            gold: Color::White,
+            bronze: Color::Gray,
+            purple: Color::White,
+            purple_dim: Color::DarkGray,
+            amber: Color::Gray,
+            text: Color::Rgb(220, 220, 220),
+            text_dim: Color::Rgb(180, 180, 180),
+            text_faint: Color::Rgb(120, 120, 120),
+            success: Color::White,
+            warn: Color::Gray,
+            error: Color::Rgb(200, 200, 200),
+        }
+    }
+
+    /// Select a theme from `BABEL_THEME` ("babel" is the default), degraded
+    /// to the detected terminal color depth
+    pub fn from_env() -> Self {
+        let theme = match std::env::var("BABEL_THEME")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "high-contrast" | "high_contrast" => Theme::high_contrast(),
+            "monochrome" | "mono" => Theme::monochrome(),
+            _ => Theme::babel(),
+        };
+        theme.adapted(ColorDepth::detect())
+    }
+
+    /// Map every palette entry onto the given color depth
+    fn adapted(self, depth: ColorDepth) -> Self {
+        Theme {
+            gold: adapt_color(self.gold, depth),
+            bronze: adapt_color(self.bronze, depth),
+            purple: adapt_color(self.purple, depth),
+            purple_dim: adapt_color(self.purple_dim, depth),
+            amber: adapt_color(self.amber, depth),
+            text: adapt_color(self.text, depth),
+            text_dim: adapt_color(self.text_dim, depth),
+            text_faint: adapt_color(self.text_faint, depth),
+            success: adapt_color(self.success, depth),
+            warn: adapt_color(self.warn, depth),
+            error: adapt_color(self.error, depth),
+        }
+    }
+}